@@ -0,0 +1,362 @@
+use crate::intersect::{intersect_subpaths, self_intersect_subpaths};
+use crate::segment::distance;
+use crate::{FillRule, Path, Segment, Subpath};
+
+/// Set operation combining the filled regions of two paths
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BooleanOp {
+    Union,
+    Intersection,
+    /// the first path with the second one cut away
+    Difference,
+    Xor,
+}
+
+impl BooleanOp {
+    fn keeps(&self, in_a: bool, in_b: bool) -> bool {
+        match self {
+            BooleanOp::Union => in_a || in_b,
+            BooleanOp::Intersection => in_a && in_b,
+            BooleanOp::Difference => in_a && !in_b,
+            BooleanOp::Xor => in_a != in_b,
+        }
+    }
+}
+
+/// Subpaths closed with a line where they are open, as filling closes them, without the
+/// ones that have no segments
+fn closed_subpaths(path: &Path) -> Vec<Subpath> {
+    path.subpaths()
+        .into_iter()
+        .filter(|subpath| !subpath.segments.is_empty())
+        .map(|mut subpath| {
+            let end = subpath.segments.last().map_or(subpath.start, Segment::end);
+            if end != subpath.start {
+                subpath.segments.push(Segment::Line {
+                    start: end,
+                    end: subpath.start,
+                });
+            }
+            subpath.closed = true;
+            subpath
+        })
+        .collect()
+}
+
+/// Winding of all subpaths around `point`
+fn winding(subpaths: &[Subpath], point: (f64, f64)) -> i32 {
+    subpaths
+        .iter()
+        .map(|subpath| subpath.winding_number(point))
+        .sum()
+}
+
+/// Parameters where each segment of an operand has to be cut, `cuts[subpath][segment]`
+type Cuts = Vec<Vec<Vec<f64>>>;
+
+/// Splits every segment at its cuts
+fn split(subpaths: &[Subpath], cuts: &Cuts, tolerance: f64) -> Vec<Segment> {
+    let mut pieces = vec![];
+
+    for (subpath, subpath_cuts) in subpaths.iter().zip(cuts) {
+        for (segment, segment_cuts) in subpath.segments.iter().zip(subpath_cuts) {
+            let mut ts = vec![0.0];
+            ts.extend(segment_cuts.iter().filter(|t| **t > 0.0 && **t < 1.0));
+            ts.push(1.0);
+            ts.sort_by(f64::total_cmp);
+
+            for pair in ts.windows(2) {
+                let piece = segment.subsegment(pair[0], pair[1]);
+
+                // cuts found twice, once from each side, leave slivers behind
+                if piece.length() > tolerance {
+                    pieces.push(piece);
+                }
+            }
+        }
+    }
+
+    pieces
+}
+
+/// Joins oriented pieces end to start into closed subpaths
+fn chain(pieces: Vec<Segment>, tolerance: f64) -> Vec<Subpath> {
+    let mut used = vec![false; pieces.len()];
+    let mut subpaths = vec![];
+
+    for first in 0..pieces.len() {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+
+        let start = pieces[first].start();
+        let mut segments = vec![pieces[first]];
+
+        loop {
+            let end = segments.last().expect("chains start with a piece").end();
+            if distance(end, start) < tolerance {
+                break;
+            }
+
+            let next = (0..pieces.len())
+                .filter(|&i| !used[i])
+                .map(|i| (i, distance(pieces[i].start(), end)))
+                .filter(|(_, gap)| *gap < tolerance)
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+
+            match next {
+                Some((i, _)) => {
+                    used[i] = true;
+                    segments.push(pieces[i]);
+                }
+                // a loose end from numerical trouble, closed with a line when written
+                None => break,
+            }
+        }
+
+        // make the pieces meet exactly, so no slivers of lines are written between them
+        let count = segments.len();
+        for i in 0..count {
+            let segment_start = if i == 0 { start } else { segments[i - 1].end() };
+            let segment_end = if i == count - 1 && distance(segments[i].end(), start) < tolerance {
+                start
+            } else {
+                segments[i].end()
+            };
+            segments[i] = segments[i].with_endpoints(segment_start, segment_end);
+        }
+
+        subpaths.push(Subpath {
+            start,
+            segments,
+            closed: true,
+        });
+    }
+
+    subpaths
+}
+
+impl Path {
+    /// Combines the regions filled by this path and `other` under `rule`. Both paths are
+    /// split where they cross each other or themselves; every piece that separates the
+    /// result from its surroundings is kept, curves included, and the pieces are joined into
+    /// closed subpaths. Outer outlines run clockwise as drawn and holes counterclockwise, so
+    /// the result fills correctly with either rule.
+    pub fn boolean(&self, other: &Path, op: BooleanOp, rule: FillRule) -> Path {
+        let operands = [closed_subpaths(self), closed_subpaths(other)];
+
+        let mut min = (f64::INFINITY, f64::INFINITY);
+        let mut max = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for subpath in operands.iter().flatten() {
+            if let Some((low, high)) = subpath.bounding_box() {
+                min = (min.0.min(low.0), min.1.min(low.1));
+                max = (max.0.max(high.0), max.1.max(high.1));
+            }
+        }
+        let scale = if min.0 <= max.0 {
+            (max.0 - min.0).max(max.1 - min.1).max(1.0)
+        } else {
+            1.0
+        };
+        let tolerance = scale * 1e-6;
+
+        let mut cuts: Vec<Cuts> = operands
+            .iter()
+            .map(|subpaths| {
+                subpaths
+                    .iter()
+                    .map(|subpath| vec![vec![]; subpath.segments.len()])
+                    .collect()
+            })
+            .collect();
+
+        for intersection in intersect_subpaths(&operands[0], &operands[1]) {
+            let (a, b) = (intersection.a, intersection.b);
+            cuts[0][a.subpath][a.segment].push(a.t);
+            cuts[1][b.subpath][b.segment].push(b.t);
+        }
+        for (index, subpaths) in operands.iter().enumerate() {
+            for intersection in self_intersect_subpaths(subpaths) {
+                let (a, b) = (intersection.a, intersection.b);
+                cuts[index][a.subpath][a.segment].push(a.t);
+                cuts[index][b.subpath][b.segment].push(b.t);
+            }
+        }
+
+        let inside = |point: (f64, f64)| {
+            op.keeps(
+                rule.is_inside(winding(&operands[0], point)),
+                rule.is_inside(winding(&operands[1], point)),
+            )
+        };
+
+        let mut kept = vec![];
+
+        for (index, subpaths) in operands.iter().enumerate() {
+            for piece in split(subpaths, &cuts[index], tolerance) {
+                let middle = piece.point_at(0.5);
+
+                // edges both paths share would otherwise be found twice
+                if index == 1
+                    && operands[0]
+                        .iter()
+                        .flat_map(|subpath| &subpath.segments)
+                        .any(|segment| segment.closest_point(middle).2 < tolerance)
+                {
+                    continue;
+                }
+
+                // probe both sides of the piece; y points down, so (-ty, tx) is on the right
+                let (tx, ty) = piece.tangent(0.5);
+                let step = tolerance * 10.0;
+                let left = inside((middle.0 + ty * step, middle.1 - tx * step));
+                let right = inside((middle.0 - ty * step, middle.1 + tx * step));
+
+                match (left, right) {
+                    (false, true) => kept.push(piece),
+                    (true, false) => kept.push(piece.reversed()),
+                    _ => {}
+                }
+            }
+        }
+
+        Path::from_subpaths(&chain(kept, tolerance * 10.0))
+    }
+
+    /// Region filled by either path, nonzero fill
+    pub fn union(&self, other: &Path) -> Path {
+        self.boolean(other, BooleanOp::Union, FillRule::NonZero)
+    }
+
+    /// Region filled by both paths, nonzero fill
+    pub fn intersection(&self, other: &Path) -> Path {
+        self.boolean(other, BooleanOp::Intersection, FillRule::NonZero)
+    }
+
+    /// Region filled by this path but not by `other`, nonzero fill
+    pub fn difference(&self, other: &Path) -> Path {
+        self.boolean(other, BooleanOp::Difference, FillRule::NonZero)
+    }
+
+    /// Region filled by exactly one of the paths, nonzero fill
+    pub fn xor(&self, other: &Path) -> Path {
+        self.boolean(other, BooleanOp::Xor, FillRule::NonZero)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPS: [BooleanOp; 4] = [
+        BooleanOp::Union,
+        BooleanOp::Intersection,
+        BooleanOp::Difference,
+        BooleanOp::Xor,
+    ];
+
+    /// Area of every operation in `OPS` order, 0 for empty results
+    fn areas(a: &str, b: &str, rule: FillRule) -> Vec<f64> {
+        let (a, b) = (Path::init(a), Path::init(b));
+        OPS.iter()
+            .map(|&op| {
+                let result = a.boolean(&b, op, rule);
+                if result.subpaths().is_empty() {
+                    0.0
+                } else {
+                    result.moments().area
+                }
+            })
+            .collect()
+    }
+
+    fn assert_areas(actual: &[f64], expected: [f64; 4]) {
+        for ((op, actual), expected) in OPS.iter().zip(actual).zip(expected) {
+            assert!(
+                (actual - expected).abs() < 1e-6 * expected.max(1.0),
+                "{:?}: {} instead of {}",
+                op,
+                actual,
+                expected
+            );
+        }
+    }
+
+    const SQUARE: &str = "M0 0 H10 V10 H0 Z";
+
+    #[test]
+    fn overlapping_squares() {
+        let result = areas(SQUARE, "M5 5 H15 V15 H5 Z", FillRule::NonZero);
+        assert_areas(&result, [175.0, 25.0, 75.0, 150.0]);
+    }
+
+    #[test]
+    fn overlapping_circles() {
+        let circle = |cx: f64| {
+            format!(
+                "M{} 0 A5 5 0 0 1 {} 0 A5 5 0 0 1 {} 0 Z",
+                cx - 5.0,
+                cx + 5.0,
+                cx - 5.0
+            )
+        };
+
+        // the lens of two circles of radius 5 whose centers are 6 apart
+        let disc = 25.0 * std::f64::consts::PI;
+        let lens = 50.0 * 0.6f64.acos() - 3.0 * 64f64.sqrt();
+
+        let result = areas(&circle(0.0), &circle(6.0), FillRule::NonZero);
+        assert_areas(
+            &result,
+            [2.0 * disc - lens, lens, disc - lens, 2.0 * (disc - lens)],
+        );
+    }
+
+    #[test]
+    fn shared_edges() {
+        // the whole right edge of the square
+        let result = areas(SQUARE, "M10 0 H20 V10 H10 Z", FillRule::NonZero);
+        assert_areas(&result, [200.0, 0.0, 100.0, 200.0]);
+
+        // part of it, collinear with the rest
+        let result = areas(SQUARE, "M10 5 H20 V15 H10 Z", FillRule::NonZero);
+        assert_areas(&result, [200.0, 0.0, 100.0, 200.0]);
+
+        // a square inside sharing the bottom edge
+        let result = areas(SQUARE, "M2 0 H8 V6 H2 Z", FillRule::NonZero);
+        assert_areas(&result, [100.0, 36.0, 64.0, 64.0]);
+    }
+
+    #[test]
+    fn identical_operands() {
+        let result = areas(SQUARE, SQUARE, FillRule::NonZero);
+        assert_areas(&result, [100.0, 100.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn empty_operand() {
+        assert_areas(
+            &areas(SQUARE, "", FillRule::NonZero),
+            [100.0, 0.0, 100.0, 100.0],
+        );
+        assert_areas(
+            &areas("", SQUARE, FillRule::NonZero),
+            [100.0, 0.0, 0.0, 100.0],
+        );
+    }
+
+    #[test]
+    fn hole_under_both_fill_rules() {
+        // both outlines clockwise, so the hole is only empty under the even-odd rule
+        let ring = "M0 0 H20 V20 H0 Z M5 5 H15 V15 H5 Z";
+        let other = "M10 0 H30 V20 H10 Z";
+
+        let even_odd = areas(ring, other, FillRule::EvenOdd);
+        assert_areas(&even_odd, [550.0, 150.0, 150.0, 400.0]);
+
+        let nonzero = areas(ring, other, FillRule::NonZero);
+        assert_areas(&nonzero, [600.0, 200.0, 200.0, 400.0]);
+    }
+}
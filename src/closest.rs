@@ -0,0 +1,213 @@
+use crate::segment::{distance, solve_quadratic};
+use crate::{Path, Segment};
+
+/// Intervals scanned for sign changes of (B(t) - P) · B'(t)
+const SCAN_INTERVALS: usize = 32;
+
+/// Nearest point on a path to a query point, in path coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Projection {
+    pub point: (f64, f64),
+    /// index into `Path::subpaths`
+    pub subpath: usize,
+    /// index into the segments of that subpath
+    pub segment: usize,
+    pub t: f64,
+    /// arc length from the start of the path
+    pub length: f64,
+    pub distance: f64,
+}
+
+impl Segment {
+    /// Parameter, position and distance of the point on the segment nearest to `point`.
+    /// Interior minima are roots of (B(t) - P) · B'(t); they are bracketed on a fine scan
+    /// and refined with safeguarded Newton steps, then compared with both endpoints. Scan
+    /// intervals whose ends show no sign change can still hold a minimum and a maximum, so
+    /// the scan points and the minimum of a cubic model of each interval are tried as well.
+    pub fn closest_point(&self, point: (f64, f64)) -> (f64, (f64, f64), f64) {
+        let gradient = |t: f64| {
+            let (x, y) = self.point_at(t);
+            let (dx, dy) = self.derivative(t);
+            (x - point.0) * dx + (y - point.1) * dy
+        };
+        let squared_distance = |t: f64| {
+            let (x, y) = self.point_at(t);
+            (x - point.0).powi(2) + (y - point.1).powi(2)
+        };
+
+        let mut candidates = vec![0.0, 1.0];
+
+        if !matches!(self, Segment::Line { .. }) {
+            let mut previous = (0.0, gradient(0.0), squared_distance(0.0));
+
+            for i in 1..=SCAN_INTERVALS {
+                let t = i as f64 / SCAN_INTERVALS as f64;
+                let current = (t, gradient(t), squared_distance(t));
+                candidates.push(t);
+
+                // the distance has a minimum where the gradient goes from negative to positive
+                if previous.1 < 0.0 && current.1 >= 0.0 {
+                    candidates.push(refine(&gradient, previous.0, t));
+                } else if let Some(middle) = interval_minimum(previous, current) {
+                    // a dip between ends that slope the same way brackets a root on one side
+                    let value = gradient(middle);
+                    candidates.push(middle);
+                    if previous.1 < 0.0 && value >= 0.0 {
+                        candidates.push(refine(&gradient, previous.0, middle));
+                    } else if value < 0.0 && current.1 >= 0.0 {
+                        candidates.push(refine(&gradient, middle, t));
+                    }
+                }
+                previous = current;
+            }
+        } else {
+            // lines have a closed form projection
+            let (start, end) = (self.start(), self.end());
+            let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+            let length_squared = dx * dx + dy * dy;
+            if length_squared > 0.0 {
+                let t = ((point.0 - start.0) * dx + (point.1 - start.1) * dy) / length_squared;
+                candidates.push(t.clamp(0.0, 1.0));
+            }
+        }
+
+        candidates
+            .into_iter()
+            .map(|t| {
+                let position = self.point_at(t);
+                (t, position, distance(position, point))
+            })
+            .min_by(|a, b| a.2.total_cmp(&b.2))
+            .expect("endpoints are always candidates")
+    }
+}
+
+/// Root of `f` in [low, high], where f(low) < 0 <= f(high), by Newton steps that fall back
+/// to bisection whenever they leave the bracket
+fn refine(f: &impl Fn(f64) -> f64, mut low: f64, mut high: f64) -> f64 {
+    let mut t = (low + high) / 2.0;
+
+    for _ in 0..50 {
+        let value = f(t);
+        if value.abs() < 1e-14 {
+            break;
+        }
+        if value < 0.0 {
+            low = t;
+        } else {
+            high = t;
+        }
+
+        // numerical derivative, the analytic one would need third derivatives of arcs
+        let h = 1e-7;
+        let slope = (f(t + h) - f(t - h)) / (2.0 * h);
+        let next = t - value / slope;
+
+        t = if slope.is_finite() && slope != 0.0 && next > low && next < high {
+            next
+        } else {
+            (low + high) / 2.0
+        };
+
+        if high - low < 1e-15 {
+            break;
+        }
+    }
+
+    t
+}
+
+/// Minimum inside an interval of the cubic that matches the squared distance and its slope,
+/// twice the gradient, at both ends given as (t, gradient, squared distance)
+fn interval_minimum(
+    (a, gradient_a, value_a): (f64, f64, f64),
+    (b, gradient_b, value_b): (f64, f64, f64),
+) -> Option<f64> {
+    // Hermite form over s in [0, 1], with the slopes scaled to s
+    let h = b - a;
+    let (slope_a, slope_b) = (2.0 * gradient_a * h, 2.0 * gradient_b * h);
+
+    // derivative of the cubic, quadratic in s
+    let qa = 6.0 * (value_a - value_b) + 3.0 * (slope_a + slope_b);
+    let qb = 6.0 * (value_b - value_a) - 4.0 * slope_a - 2.0 * slope_b;
+    let qc = slope_a;
+
+    solve_quadratic(qa, qb, qc)
+        .into_iter()
+        .find(|&s| s > 0.0 && s < 1.0 && 2.0 * qa * s + qb > 0.0)
+        .map(|s| a + s * h)
+}
+
+/// Distance from `point` to an axis-aligned box, 0 inside it
+fn box_distance(point: (f64, f64), (min, max): ((f64, f64), (f64, f64))) -> f64 {
+    let dx = (min.0 - point.0).max(point.0 - max.0).max(0.0);
+    let dy = (min.1 - point.1).max(point.1 - max.1).max(0.0);
+    dx.hypot(dy)
+}
+
+impl Path {
+    /// Nearest point on the path to `point`, `None` for a path without segments
+    pub fn closest_point(&self, point: (f64, f64)) -> Option<Projection> {
+        let mut best: Option<Projection> = None;
+        let mut offset = 0.0;
+
+        for (subpath_index, subpath) in self.subpaths().iter().enumerate() {
+            for (index, segment) in subpath.segments.iter().enumerate() {
+                let segment_length = segment.length();
+
+                // skip segments whose bounding box is already further away than the best hit
+                let reachable = best.is_none_or(|best| {
+                    box_distance(point, segment.bounding_box()) <= best.distance
+                });
+
+                if reachable {
+                    let (t, position, distance) = segment.closest_point(point);
+                    if best.is_none_or(|best| distance < best.distance) {
+                        best = Some(Projection {
+                            point: position,
+                            subpath: subpath_index,
+                            segment: index,
+                            t,
+                            length: offset + segment.length_between(0.0, t),
+                            distance,
+                        });
+                    }
+                }
+
+                offset += segment_length;
+            }
+        }
+
+        best
+    }
+
+    /// Distance from `point` to the nearest point on the path
+    pub fn distance_to(&self, point: (f64, f64)) -> Option<f64> {
+        self.closest_point(point)
+            .map(|projection| projection.distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_minimum_hidden_between_scan_points() {
+        // near the tip of a thin ellipse the distance to a point inside has two minima, and
+        // the closer one shares its scan interval with the maximum between them
+        let on_ellipse = |angle: f64| (1000.0 * angle.cos(), angle.sin());
+        let arc = Segment::Arc {
+            start: on_ellipse(-0.05),
+            radii: (1000.0, 1.0),
+            rotation: 0.0,
+            large_arc: false,
+            sweep: true,
+            end: on_ellipse(1.87),
+        };
+
+        let (_, _, distance) = arc.closest_point((999.5, -0.01));
+        assert!((distance - 0.021608).abs() < 1e-5, "{}", distance);
+    }
+}
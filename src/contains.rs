@@ -0,0 +1,144 @@
+use crate::{FillRule, Path, Segment, Subpath};
+
+/// Bisection steps when solving y(t) = y on a monotone piece, enough for full f64 precision
+const BISECTION_STEPS: usize = 60;
+
+impl Segment {
+    /// Signed crossings of the ray from `point` towards +x: +1 where the segment runs
+    /// towards larger y, -1 where it runs towards smaller y. The segment is split at its
+    /// y extrema so every piece is monotone, and each crossing is solved on the curve itself.
+    pub fn winding_contribution(&self, point: (f64, f64)) -> i32 {
+        let (min, max) = self.bounding_box();
+
+        // the ray cannot hit a segment that is entirely above, below or left of the point
+        if point.1 < min.1 || point.1 > max.1 || max.0 <= point.0 {
+            return 0;
+        }
+
+        let mut ts = vec![0.0];
+        ts.extend(self.extrema(1));
+        ts.push(1.0);
+
+        let mut winding = 0;
+
+        for pair in ts.windows(2) {
+            let (t0, t1) = (pair[0], pair[1]);
+            let (y0, y1) = (self.point_at(t0).1, self.point_at(t1).1);
+
+            // half-open on y, so a ray through a joint between pieces counts once
+            let direction = if y0 <= point.1 && point.1 < y1 {
+                1
+            } else if y1 <= point.1 && point.1 < y0 {
+                -1
+            } else {
+                continue;
+            };
+
+            let (mut low, mut high) = (t0, t1);
+            for _ in 0..BISECTION_STEPS {
+                let middle = (low + high) / 2.0;
+                let below = self.point_at(middle).1 < point.1;
+
+                // y grows from low to high on upward pieces and shrinks on downward ones
+                if below == (direction == 1) {
+                    low = middle;
+                } else {
+                    high = middle;
+                }
+            }
+
+            if self.point_at((low + high) / 2.0).0 > point.0 {
+                winding += direction;
+            }
+        }
+
+        winding
+    }
+}
+
+impl Subpath {
+    /// Winding number of the subpath around `point`. Open subpaths are closed with a
+    /// straight line, as SVG does when filling.
+    pub fn winding_number(&self, point: (f64, f64)) -> i32 {
+        let mut winding: i32 = self
+            .segments
+            .iter()
+            .map(|segment| segment.winding_contribution(point))
+            .sum();
+
+        let end = self.segments.last().map_or(self.start, Segment::end);
+        if end != self.start {
+            winding += Segment::Line {
+                start: end,
+                end: self.start,
+            }
+            .winding_contribution(point);
+        }
+
+        winding
+    }
+}
+
+impl Path {
+    /// Winding number of the whole path around `point`, in path (SVG) coordinates
+    pub fn winding_number(&self, point: (f64, f64)) -> i32 {
+        self.subpaths()
+            .iter()
+            .map(|subpath| subpath.winding_number(point))
+            .sum()
+    }
+
+    /// Whether `point`, in path (SVG) coordinates, lies inside the path filled with `rule`
+    pub fn contains(&self, point: (f64, f64), rule: FillRule) -> bool {
+        rule.is_inside(self.winding_number(point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn holes_under_both_fill_rules() {
+        // the inner square runs the same way as the outer one, winding twice around its inside
+        let same = Path::init("M0 0 H20 V20 H0 Z M5 5 H15 V15 H5 Z");
+        // and here the other way, cancelling the outer one
+        let opposite = Path::init("M0 0 H20 V20 H0 Z M5 5 V15 H15 V5 Z");
+
+        for (point, same_winding, opposite_winding) in [
+            ((2.0, 2.0), 1, 1),
+            ((10.0, 10.0), 2, 0),
+            ((25.0, 10.0), 0, 0),
+            // rays along an edge and through vertices count once
+            ((2.0, 5.0), 1, 1),
+        ] {
+            assert_eq!(same.winding_number(point), same_winding, "{:?}", point);
+            assert_eq!(
+                opposite.winding_number(point),
+                opposite_winding,
+                "{:?}",
+                point
+            );
+        }
+
+        assert!(same.contains((10.0, 10.0), FillRule::NonZero));
+        assert!(!same.contains((10.0, 10.0), FillRule::EvenOdd));
+        assert!(!opposite.contains((10.0, 10.0), FillRule::NonZero));
+        assert!(!opposite.contains((10.0, 10.0), FillRule::EvenOdd));
+        assert!(same.contains((2.0, 2.0), FillRule::EvenOdd));
+        assert!(!same.contains((25.0, 10.0), FillRule::NonZero));
+    }
+
+    #[test]
+    fn tests_against_the_curves_themselves() {
+        let circle = Path::init("M-5 0 A5 5 0 0 1 5 0 A5 5 0 0 1 -5 0 Z");
+
+        assert!(circle.contains((0.0, 0.0), FillRule::NonZero));
+        assert!(circle.contains((3.5, 3.5), FillRule::NonZero));
+        // inside the square through the ends of the arcs, outside the circle
+        assert!(!circle.contains((3.6, 3.6), FillRule::NonZero));
+        // open subpaths are closed with a line
+        assert!(Path::init("M0 0 H10 V10").contains((8.0, 2.0), FillRule::NonZero));
+        assert!(!Path::init("M0 0 H10 V10").contains((2.0, 8.0), FillRule::NonZero));
+    }
+}
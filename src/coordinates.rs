@@ -0,0 +1,57 @@
+/// Mapping from path coordinates (SVG, y down) into the coordinate system of an output:
+/// scaled, optionally mirrored so y points up, then moved to `origin`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CoordinateSystem {
+    /// output units per path unit
+    pub scale: f64,
+    /// whether y points up in the output
+    pub flip_y: bool,
+    /// where the path origin ends up, in output units
+    pub origin: (f64, f64),
+}
+
+impl CoordinateSystem {
+    /// The y up system of `points` and `samples`
+    pub const CARTESIAN: CoordinateSystem = CoordinateSystem {
+        scale: 1.0,
+        flip_y: true,
+        origin: (0.0, 0.0),
+    };
+
+    /// Path coordinates themselves
+    pub const SVG: CoordinateSystem = CoordinateSystem {
+        scale: 1.0,
+        flip_y: false,
+        origin: (0.0, 0.0),
+    };
+
+    pub fn map(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        let y = if self.flip_y { -y } else { y };
+        (
+            x * self.scale + self.origin.0,
+            y * self.scale + self.origin.1,
+        )
+    }
+
+    /// Turns an output point back into path coordinates
+    pub fn unmap(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        let (x, y) = (
+            (x - self.origin.0) / self.scale,
+            (y - self.origin.1) / self.scale,
+        );
+        (x, if self.flip_y { -y } else { y })
+    }
+
+    /// Maps a direction, which is mirrored but neither scaled nor moved, so unit vectors
+    /// stay unit vectors
+    pub fn map_direction(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        (x, if self.flip_y { -y } else { y })
+    }
+}
+
+impl Default for CoordinateSystem {
+    fn default() -> Self {
+        CoordinateSystem::CARTESIAN
+    }
+}
@@ -0,0 +1,386 @@
+use crate::segment::distance;
+use crate::{CoordinateSystem, Path, Sample, SampleColumn, Segment, Subpath};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+
+/// Layout of a CSV file of samples. The default is the `x,y` rows of
+/// `save_points_to_file`: comma separated, no header, full precision.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CsvOptions {
+    pub delimiter: char,
+    /// whether the first row names the columns, as `SampleColumn::name` does; columns with
+    /// other names are skipped
+    pub header: bool,
+    /// what the fields of a row are when there is no header; further fields are skipped.
+    /// Written files have exactly these columns.
+    pub columns: Vec<SampleColumn>,
+    /// decimal places written for values, the shortest exact form when `None`
+    pub precision: Option<usize>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            delimiter: ',',
+            header: false,
+            columns: vec![SampleColumn::X, SampleColumn::Y],
+            precision: None,
+        }
+    }
+}
+
+fn invalid(line: usize, message: String) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("line {}: {}", line, message),
+    )
+}
+
+/// Values of one row by column, in the order the columns were given
+struct Row {
+    values: Vec<(SampleColumn, f64)>,
+}
+
+impl Row {
+    fn get(&self, column: SampleColumn) -> Option<f64> {
+        self.values
+            .iter()
+            .find(|(c, _)| *c == column)
+            .map(|(_, value)| *value)
+    }
+}
+
+/// Parses the rows of a CSV file into groups, one for every subpath. A blank line ends a
+/// subpath, and so does a change in the subpath column if there is one.
+fn read_rows<R: BufRead>(reader: R, options: &CsvOptions) -> io::Result<Vec<Vec<Row>>> {
+    let mut layout: Option<Vec<Option<SampleColumn>>> =
+        (!options.header).then(|| options.columns.iter().copied().map(Some).collect());
+    let mut groups: Vec<Vec<Row>> = vec![vec![]];
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let number = index + 1;
+
+        if line.trim().is_empty() {
+            if !groups[groups.len() - 1].is_empty() {
+                groups.push(vec![]);
+            }
+            continue;
+        }
+
+        let fields = line.split(options.delimiter).map(str::trim);
+
+        let Some(columns) = &layout else {
+            layout = Some(fields.map(SampleColumn::from_name).collect());
+            continue;
+        };
+
+        let mut values = vec![];
+        for (field, column) in fields.zip(columns) {
+            if let Some(column) = column {
+                let value = field
+                    .parse()
+                    .map_err(|_| invalid(number, format!("{:?} is not a number", field)))?;
+                values.push((*column, value));
+            }
+        }
+
+        let row = Row { values };
+        for column in [SampleColumn::X, SampleColumn::Y] {
+            if row.get(column).is_none() {
+                return Err(invalid(number, format!("missing {}", column.name())));
+            }
+        }
+
+        let group = groups.len() - 1;
+        let changes_subpath = groups[group]
+            .last()
+            .is_some_and(|last| last.get(SampleColumn::Subpath) != row.get(SampleColumn::Subpath));
+        if changes_subpath {
+            groups.push(vec![]);
+        }
+
+        let group = groups.len() - 1;
+        groups[group].push(row);
+    }
+
+    groups.retain(|group| !group.is_empty());
+    Ok(groups)
+}
+
+/// Samples for every group of rows. Each group is treated as a polyline: what the file
+/// leaves out is filled in from it, with the sample at the end of each line segment, the
+/// arc length along the polyline, the tangent of the neighbouring step and no curvature.
+fn read_groups<R: BufRead>(reader: R, options: &CsvOptions) -> io::Result<Vec<Vec<Sample>>> {
+    let mut groups = vec![];
+    let mut length = 0.0;
+
+    for (subpath, rows) in read_rows(reader, options)?.iter().enumerate() {
+        let mut samples = vec![];
+        let points: Vec<(f64, f64)> = rows
+            .iter()
+            .map(|row| {
+                (
+                    row.get(SampleColumn::X).unwrap_or_default(),
+                    row.get(SampleColumn::Y).unwrap_or_default(),
+                )
+            })
+            .collect();
+
+        for (i, row) in rows.iter().enumerate() {
+            if i > 0 {
+                length += distance(points[i - 1], points[i]);
+            }
+
+            let (from, to) = if i + 1 < points.len() {
+                (points[i], points[i + 1])
+            } else {
+                (points[i.saturating_sub(1)], points[i])
+            };
+            let step = distance(from, to);
+            let tangent = if step > 0.0 {
+                ((to.0 - from.0) / step, (to.1 - from.1) / step)
+            } else {
+                (0.0, 0.0)
+            };
+
+            samples.push(Sample {
+                point: points[i],
+                subpath: row
+                    .get(SampleColumn::Subpath)
+                    .map_or(subpath, |value| value as usize),
+                segment: row
+                    .get(SampleColumn::Segment)
+                    .map_or(i.saturating_sub(1), |value| value as usize),
+                t: row
+                    .get(SampleColumn::T)
+                    .unwrap_or(if i == 0 { 0.0 } else { 1.0 }),
+                length: row.get(SampleColumn::Length).unwrap_or(length),
+                tangent: (
+                    row.get(SampleColumn::TangentX).unwrap_or(tangent.0),
+                    row.get(SampleColumn::TangentY).unwrap_or(tangent.1),
+                ),
+                curvature: row.get(SampleColumn::Curvature).unwrap_or_default(),
+            });
+        }
+
+        groups.push(samples);
+    }
+
+    Ok(groups)
+}
+
+/// Reads samples written as CSV, with what the file leaves out taken from the polyline
+/// through each group of rows
+pub fn read_csv_samples<R: BufRead>(reader: R, options: &CsvOptions) -> io::Result<Vec<Sample>> {
+    Ok(read_groups(reader, options)?.concat())
+}
+
+/// Writes samples as CSV, one row each. Subpaths are separated by a blank line, so
+/// `read_csv_samples` finds them again even without a subpath column.
+pub fn write_csv_samples<W: Write>(
+    writer: &mut W,
+    samples: &[Sample],
+    options: &CsvOptions,
+) -> io::Result<()> {
+    let delimiter = options.delimiter.to_string();
+
+    if options.header {
+        let names: Vec<&str> = options.columns.iter().map(SampleColumn::name).collect();
+        writeln!(writer, "{}", names.join(&delimiter))?;
+    }
+
+    for (i, sample) in samples.iter().enumerate() {
+        if i > 0 && samples[i - 1].subpath != sample.subpath {
+            writeln!(writer)?;
+        }
+
+        let fields: Vec<String> = options
+            .columns
+            .iter()
+            .map(|column| match (column, options.precision) {
+                // indices stay whole numbers whatever the precision
+                (SampleColumn::Segment, _) => sample.segment.to_string(),
+                (SampleColumn::Subpath, _) => sample.subpath.to_string(),
+                // adding zero turns the -0 of flipped coordinates into 0
+                (_, Some(precision)) => format!("{:.*}", precision, column.value(sample) + 0.0),
+                (_, None) => (column.value(sample) + 0.0).to_string(),
+            })
+            .collect();
+        writeln!(writer, "{}", fields.join(&delimiter))?;
+    }
+
+    Ok(())
+}
+
+pub fn save_csv_samples(
+    filename: &str,
+    samples: &[Sample],
+    options: &CsvOptions,
+) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(filename)?);
+    write_csv_samples(&mut file, samples, options)?;
+    file.flush()
+}
+
+/// `read_csv_samples` from a file
+pub fn load_csv_samples(filename: &str, options: &CsvOptions) -> io::Result<Vec<Sample>> {
+    read_csv_samples(BufReader::new(File::open(filename)?), options)
+}
+
+impl Path {
+    /// Polyline through the points of a CSV file, one subpath per group of rows. The points
+    /// are cartesian like `points`, so they are flipped back into path coordinates, and a
+    /// group that ends where it starts is closed.
+    pub fn read_csv<R: BufRead>(reader: R, options: &CsvOptions) -> io::Result<Path> {
+        let subpaths: Vec<Subpath> = read_groups(reader, options)?
+            .iter()
+            .map(|run| {
+                let points: Vec<(f64, f64)> = run
+                    .iter()
+                    .map(|sample| CoordinateSystem::CARTESIAN.unmap(sample.point))
+                    .collect();
+                let closed = points.len() > 2 && points[0] == points[points.len() - 1];
+
+                Subpath {
+                    start: points[0],
+                    segments: points
+                        .windows(2)
+                        .map(|pair| Segment::Line {
+                            start: pair[0],
+                            end: pair[1],
+                        })
+                        .collect(),
+                    closed,
+                }
+            })
+            .collect();
+
+        Ok(Path::from_subpaths(&subpaths))
+    }
+
+    /// `read_csv` from a file, such as one written by `save_csv`. Files written by
+    /// `save_points_to_file` hold the points in no particular order, so they do not read
+    /// back as the path they came from.
+    pub fn load_csv(filename: &str, options: &CsvOptions) -> io::Result<Path> {
+        Path::read_csv(BufReader::new(File::open(filename)?), options)
+    }
+
+    /// Writes `samples` as CSV
+    pub fn write_csv<W: Write>(&self, writer: &mut W, options: &CsvOptions) -> io::Result<()> {
+        write_csv_samples(writer, &self.samples(), options)
+    }
+
+    pub fn save_csv(&self, filename: &str, options: &CsvOptions) -> io::Result<()> {
+        save_csv_samples(filename, &self.samples(), options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [SampleColumn; 9] = [
+        SampleColumn::X,
+        SampleColumn::Y,
+        SampleColumn::Length,
+        SampleColumn::T,
+        SampleColumn::Segment,
+        SampleColumn::Subpath,
+        SampleColumn::TangentX,
+        SampleColumn::TangentY,
+        SampleColumn::Curvature,
+    ];
+
+    fn written(samples: &[Sample], options: &CsvOptions) -> String {
+        let mut out = vec![];
+        write_csv_samples(&mut out, samples, options).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn sample(point: (f64, f64), subpath: usize) -> Sample {
+        Sample {
+            point,
+            subpath,
+            segment: 0,
+            t: 0.0,
+            length: 0.0,
+            tangent: (1.0, 0.0),
+            curvature: 0.0,
+        }
+    }
+
+    #[test]
+    fn every_column_reads_back_exactly() {
+        let samples = Path::init("M0 0 C3 4 7 -4 10 0 M0 5 A2 2 0 0 1 4 5").samples();
+        let options = CsvOptions {
+            delimiter: ';',
+            header: true,
+            columns: ALL.to_vec(),
+            precision: None,
+        };
+
+        let text = written(&samples, &options);
+        assert!(text.starts_with(
+            "x;y;length;t;segment;subpath;tangent_x;tangent_y;curvature\n0;0;0;0;0;0;"
+        ));
+
+        let read = read_csv_samples(text.as_bytes(), &options).unwrap();
+        assert_eq!(read, samples);
+    }
+
+    #[test]
+    fn blank_lines_separate_subpaths() {
+        let samples = [
+            sample((0.0, 0.0), 0),
+            sample((3.0, 4.0), 0),
+            sample((0.0, -0.0), 1),
+            sample((0.0, 2.0), 1),
+        ];
+
+        let text = written(&samples, &CsvOptions::default());
+        assert_eq!(text, "0,0\n3,4\n\n0,0\n0,2\n");
+
+        // what the two columns leave out comes from the polyline through each group
+        let read = read_csv_samples(text.as_bytes(), &CsvOptions::default()).unwrap();
+        let subpaths: Vec<usize> = read.iter().map(|sample| sample.subpath).collect();
+        let lengths: Vec<f64> = read.iter().map(|sample| sample.length).collect();
+        assert_eq!(subpaths, [0, 0, 1, 1]);
+        assert_eq!(lengths, [0.0, 5.0, 5.0, 7.0]);
+        assert_eq!(read[0].tangent, (0.6, 0.8));
+        assert_eq!(read[3].tangent, (0.0, 1.0));
+    }
+
+    #[test]
+    fn header_names_pick_the_columns() {
+        let text = "label,y,x\na,2,1\nb,4,3\n";
+        let options = CsvOptions {
+            header: true,
+            ..CsvOptions::default()
+        };
+
+        let read = read_csv_samples(text.as_bytes(), &options).unwrap();
+        let points: Vec<(f64, f64)> = read.iter().map(|sample| sample.point).collect();
+        assert_eq!(points, [(1.0, 2.0), (3.0, 4.0)]);
+
+        let error = read_csv_samples("1,2\n3\n".as_bytes(), &CsvOptions::default()).unwrap_err();
+        assert_eq!(error.to_string(), "line 2: missing y");
+    }
+
+    #[test]
+    fn paths_read_back_in_path_coordinates() {
+        let path = Path::init("M0 0 H10 V10 H0 Z M20 0 H30");
+        let mut out = vec![];
+        path.write_csv(&mut out, &CsvOptions::default()).unwrap();
+
+        let read = Path::read_csv(out.as_slice(), &CsvOptions::default()).unwrap();
+        let subpaths = read.subpaths();
+        assert_eq!(subpaths.len(), 2);
+        assert!(subpaths[0].closed && !subpaths[1].closed);
+        assert_eq!(subpaths[0].start, (0.0, 0.0));
+        let length = |path: &Path| path.segments().iter().map(Segment::length).sum::<f64>();
+        assert!((length(&read) - length(&path)).abs() < 1e-9);
+        assert_eq!(read.bounding_box(), path.bounding_box());
+    }
+}
@@ -0,0 +1,168 @@
+use crate::sample::LengthTable;
+use crate::{Path, Subpath};
+
+/// Most pattern periods a subpath is cut into. Finer patterns leave the subpath solid, the
+/// dashes would be too small to draw and too many to store, and once a period gets close to
+/// the precision of the lengths along the subpath the positions stop moving.
+const MAX_PERIODS: f64 = 100_000.0;
+
+/// Lengths along a subpath of length `total` that are drawn for `pattern`, as (start, end)
+/// pairs. `pattern` alternates dash and gap lengths and has an even number of entries, and
+/// its period fits at most `MAX_PERIODS` times into `total`.
+fn dash_ranges(pattern: &[f64], offset: f64, total: f64) -> Vec<(f64, f64)> {
+    let period: f64 = pattern.iter().sum();
+    debug_assert!(total / period <= MAX_PERIODS);
+
+    // find where in the pattern the subpath starts
+    let mut phase = offset.rem_euclid(period);
+    let mut index = 0;
+    while phase > 0.0 && phase >= pattern[index] {
+        phase -= pattern[index];
+        index = (index + 1) % pattern.len();
+    }
+
+    let mut ranges = vec![];
+    let mut position = 0.0;
+    let mut remaining = pattern[index] - phase;
+
+    while position < total {
+        let end = (position + remaining).min(total);
+        if index % 2 == 0 {
+            ranges.push((position, end));
+        }
+
+        position += remaining;
+        index = (index + 1) % pattern.len();
+        remaining = pattern[index];
+    }
+
+    ranges
+}
+
+impl Path {
+    /// Splits the path into the dashes SVG draws for `stroke-dasharray` and
+    /// `stroke-dashoffset`, each dash its own subpath made of exact pieces of the original
+    /// segments. The pattern restarts at every subpath, and on closed subpaths a dash running
+    /// through the start is kept in one piece. An odd number of values is repeated as SVG
+    /// does; an empty pattern, negative values or an all-zero pattern leave the path solid,
+    /// and so does a pattern that would repeat more than 100 000 times along a subpath.
+    pub fn dash(&self, dasharray: &[f64], offset: f64) -> Path {
+        let subpaths = self.subpaths();
+
+        if dasharray.is_empty()
+            || dasharray
+                .iter()
+                .any(|length| *length < 0.0 || !length.is_finite())
+            || dasharray.iter().sum::<f64>() <= 0.0
+        {
+            return Path::from_subpaths(&subpaths);
+        }
+
+        let mut pattern = dasharray.to_vec();
+        if pattern.len() % 2 == 1 {
+            pattern.extend_from_slice(dasharray);
+        }
+
+        let mut dashes: Vec<Subpath> = vec![];
+
+        for subpath in &subpaths {
+            let table = LengthTable::new(std::slice::from_ref(subpath));
+            if table.total <= 0.0 {
+                continue;
+            }
+            if table.total / pattern.iter().sum::<f64>() > MAX_PERIODS {
+                dashes.push(subpath.clone());
+                continue;
+            }
+
+            let ranges = dash_ranges(&pattern, offset, table.total);
+            let mut pieces: Vec<Subpath> = ranges
+                .iter()
+                .flat_map(|&(start, end)| table.slice(start, end))
+                .collect();
+
+            // a dash through the start of a closed subpath is drawn as one
+            let wraps = match (ranges.first(), ranges.last()) {
+                (Some(first), Some(last)) => {
+                    ranges.len() > 1 && first.0 == 0.0 && last.1 == table.total
+                }
+                _ => false,
+            };
+
+            if subpath.closed && wraps {
+                let first = pieces.remove(0);
+                if let Some(last) = pieces.last_mut() {
+                    last.segments.extend(first.segments);
+                }
+            }
+
+            dashes.extend(pieces);
+        }
+
+        Path::from_subpaths(&dashes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lengths(path: &Path) -> Vec<f64> {
+        path.subpaths()
+            .iter()
+            .map(|subpath| {
+                let length: f64 = subpath.segments.iter().map(|s| s.length()).sum();
+                (length * 1e9).round() / 1e9
+            })
+            .collect()
+    }
+
+    #[test]
+    fn offset_shifts_the_pattern() {
+        assert_eq!(
+            dash_ranges(&[2.0, 1.0], 0.0, 7.0),
+            vec![(0.0, 2.0), (3.0, 5.0), (6.0, 7.0)]
+        );
+        // starting 2.5 into the pattern, half way through the gap
+        assert_eq!(
+            dash_ranges(&[2.0, 1.0], 2.5, 7.0),
+            vec![(0.5, 2.5), (3.5, 5.5), (6.5, 7.0)]
+        );
+        // negative offsets move the pattern the other way
+        assert_eq!(dash_ranges(&[2.0, 1.0], -1.0, 4.0), vec![(1.0, 3.0)]);
+    }
+
+    #[test]
+    fn odd_patterns_are_repeated() {
+        // [3] is read as [3, 3], [1, 2, 3] as [1, 2, 3, 1, 2, 3]
+        let line = Path::init("M0 0 H10");
+        assert_eq!(lengths(&line.dash(&[3.0], 0.0)), vec![3.0, 3.0]);
+        assert_eq!(
+            lengths(&line.dash(&[1.0, 2.0, 3.0], 0.0)),
+            vec![1.0, 3.0, 2.0]
+        );
+    }
+
+    #[test]
+    fn joins_a_dash_through_the_start_of_a_closed_subpath() {
+        // a square 40 around, the last dash runs from 39 through the start to 1
+        let square = Path::init("M0 0 H10 V10 H0 Z");
+        let dashed = square.dash(&[4.0, 2.0], 3.0);
+        let subpaths = dashed.subpaths();
+
+        assert_eq!(lengths(&dashed), vec![4.0, 4.0, 4.0, 4.0, 4.0, 4.0, 2.0]);
+        let joined = subpaths.last().unwrap();
+        assert_eq!(joined.start, (0.0, 1.0));
+        assert_eq!(joined.segments.last().unwrap().end(), (1.0, 0.0));
+
+        // on the open outline both ends stay apart
+        let open = Path::init("M0 0 H10 V10 H0 V0").dash(&[4.0, 2.0], 3.0);
+        assert_eq!(open.subpaths().len(), 8);
+    }
+
+    #[test]
+    fn leaves_too_fine_patterns_solid() {
+        let line = Path::init("M0 0 H1000");
+        assert_eq!(lengths(&line.dash(&[1e-9, 1e-9], 0.0)), vec![1000.0]);
+    }
+}
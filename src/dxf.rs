@@ -0,0 +1,585 @@
+use crate::{CoordinateSystem, Path, Segment};
+use std::f64::consts::PI;
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+/// Settings for `write_dxf`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DxfOptions {
+    /// where path coordinates end up in the drawing, y up by default as CAD expects
+    pub coordinates: CoordinateSystem,
+    /// write every subpath as one LWPOLYLINE within this tolerance, in path units, instead
+    /// of exact LINE, SPLINE, ARC and ELLIPSE entities
+    pub flatten: Option<f64>,
+}
+
+impl Default for DxfOptions {
+    fn default() -> Self {
+        DxfOptions {
+            coordinates: CoordinateSystem::CARTESIAN,
+            flatten: None,
+        }
+    }
+}
+
+const CONTINUOUS: &str = "Continuous";
+const MODEL_SPACE: &str = "*Model_Space";
+const PAPER_SPACE: &str = "*Paper_Space";
+
+/// Characters DXF does not allow in layer names
+const RESERVED: &[char] = &['<', '>', '/', '\\', '"', ':', ';', '?', '*', '|', '=', '`'];
+
+/// A valid layer name close to `name`, the default layer `0` for an empty one
+fn layer_name(name: &str) -> String {
+    let name: String = name
+        .trim()
+        .chars()
+        .map(|c| if RESERVED.contains(&c) { '_' } else { c })
+        .collect();
+
+    if name.is_empty() {
+        "0".to_string()
+    } else {
+        name
+    }
+}
+
+/// Writes group codes and values, one per line as ASCII DXF has them
+struct DxfWriter<'a, W: Write> {
+    writer: &'a mut W,
+    system: CoordinateSystem,
+    /// handle given to the next object, every object in an R2000 drawing has one
+    next_handle: u64,
+    /// block record of the entities written, model space once the tables are out
+    owner: u64,
+}
+
+impl<W: Write> DxfWriter<'_, W> {
+    fn pair(&mut self, code: u32, value: impl Display) -> io::Result<()> {
+        write!(self.writer, "{:>3}\n{}\n", code, value)
+    }
+
+    /// Gives the object just started the next handle under `code`, usually 5, and names
+    /// the object that owns it, 0 for none
+    fn handle(&mut self, code: u32, owner: u64) -> io::Result<u64> {
+        let handle = self.next_handle;
+        self.next_handle += 1;
+        self.pair(code, format!("{:X}", handle))?;
+        self.pair(330, format!("{:X}", owner))?;
+        Ok(handle)
+    }
+
+    fn table(&mut self, name: &str, count: usize) -> io::Result<u64> {
+        self.pair(0, "TABLE")?;
+        self.pair(2, name)?;
+        let handle = self.handle(5, 0)?;
+        self.pair(100, "AcDbSymbolTable")?;
+        self.pair(70, count)?;
+        Ok(handle)
+    }
+
+    fn record(&mut self, kind: &str, table: u64, subclass: &str, name: &str) -> io::Result<u64> {
+        self.pair(0, kind)?;
+        // dimension styles keep their handle under 105, 5 being one of their variables
+        let handle = self.handle(if kind == "DIMSTYLE" { 105 } else { 5 }, table)?;
+        self.pair(100, "AcDbSymbolTableRecord")?;
+        self.pair(100, subclass)?;
+        self.pair(2, name)?;
+        self.pair(70, 0)?;
+        Ok(handle)
+    }
+
+    /// The symbol tables an R2000 drawing needs, with the default entries AutoCAD expects
+    /// and one entry per layer. Returns the block records of model and paper space.
+    fn tables(&mut self, layers: &[String]) -> io::Result<(u64, u64)> {
+        self.pair(0, "SECTION")?;
+        self.pair(2, "TABLES")?;
+
+        self.table("VPORT", 0)?;
+        self.pair(0, "ENDTAB")?;
+
+        let table = self.table("LTYPE", 3)?;
+        for (name, description) in [("ByBlock", ""), ("ByLayer", ""), (CONTINUOUS, "Solid line")] {
+            self.record("LTYPE", table, "AcDbLinetypeTableRecord", name)?;
+            self.pair(3, description)?;
+            self.pair(72, 65)?;
+            self.pair(73, 0)?;
+            self.number(40, 0.0)?;
+        }
+        self.pair(0, "ENDTAB")?;
+
+        let table = self.table("LAYER", layers.len())?;
+        for name in layers {
+            self.record("LAYER", table, "AcDbLayerTableRecord", name)?;
+            self.pair(62, 7)?;
+            self.pair(6, CONTINUOUS)?;
+        }
+        self.pair(0, "ENDTAB")?;
+
+        let table = self.table("STYLE", 1)?;
+        self.record("STYLE", table, "AcDbTextStyleTableRecord", "Standard")?;
+        self.number(40, 0.0)?;
+        self.number(41, 1.0)?;
+        self.number(50, 0.0)?;
+        self.pair(71, 0)?;
+        self.number(42, 2.5)?;
+        self.pair(3, "txt")?;
+        self.pair(4, "")?;
+        self.pair(0, "ENDTAB")?;
+
+        self.table("VIEW", 0)?;
+        self.pair(0, "ENDTAB")?;
+        self.table("UCS", 0)?;
+        self.pair(0, "ENDTAB")?;
+
+        let table = self.table("APPID", 1)?;
+        self.record("APPID", table, "AcDbRegAppTableRecord", "ACAD")?;
+        self.pair(0, "ENDTAB")?;
+
+        let table = self.table("DIMSTYLE", 1)?;
+        self.pair(100, "AcDbDimStyleTable")?;
+        self.record("DIMSTYLE", table, "AcDbDimStyleTableRecord", "Standard")?;
+        self.pair(0, "ENDTAB")?;
+
+        let table = self.table("BLOCK_RECORD", 2)?;
+        let model = self.record("BLOCK_RECORD", table, "AcDbBlockTableRecord", MODEL_SPACE)?;
+        let paper = self.record("BLOCK_RECORD", table, "AcDbBlockTableRecord", PAPER_SPACE)?;
+        self.pair(0, "ENDTAB")?;
+
+        self.pair(0, "ENDSEC")?;
+        Ok((model, paper))
+    }
+
+    /// The empty blocks behind model and paper space, the entities of model space are
+    /// written to the ENTITIES section instead
+    fn blocks(&mut self, (model, paper): (u64, u64)) -> io::Result<()> {
+        self.pair(0, "SECTION")?;
+        self.pair(2, "BLOCKS")?;
+
+        for (name, record) in [(MODEL_SPACE, model), (PAPER_SPACE, paper)] {
+            self.pair(0, "BLOCK")?;
+            self.handle(5, record)?;
+            self.pair(100, "AcDbEntity")?;
+            if record == paper {
+                self.pair(67, 1)?;
+            }
+            self.pair(8, "0")?;
+            self.pair(100, "AcDbBlockBegin")?;
+            self.pair(2, name)?;
+            self.pair(70, 0)?;
+            self.number(10, 0.0)?;
+            self.number(20, 0.0)?;
+            self.number(30, 0.0)?;
+            self.pair(3, name)?;
+            self.pair(1, "")?;
+
+            self.pair(0, "ENDBLK")?;
+            self.handle(5, record)?;
+            self.pair(100, "AcDbEntity")?;
+            if record == paper {
+                self.pair(67, 1)?;
+            }
+            self.pair(8, "0")?;
+            self.pair(100, "AcDbBlockEnd")?;
+        }
+
+        self.pair(0, "ENDSEC")
+    }
+
+    /// The root dictionary with the group dictionary every drawing has
+    fn objects(&mut self) -> io::Result<()> {
+        self.pair(0, "SECTION")?;
+        self.pair(2, "OBJECTS")?;
+
+        self.pair(0, "DICTIONARY")?;
+        let root = self.handle(5, 0)?;
+        self.pair(100, "AcDbDictionary")?;
+        self.pair(281, 1)?;
+        self.pair(3, "ACAD_GROUP")?;
+        self.pair(350, format!("{:X}", self.next_handle))?;
+
+        self.pair(0, "DICTIONARY")?;
+        self.handle(5, root)?;
+        self.pair(100, "AcDbDictionary")?;
+        self.pair(281, 1)?;
+
+        self.pair(0, "ENDSEC")
+    }
+
+    fn number(&mut self, code: u32, value: f64) -> io::Result<()> {
+        // adding zero turns -0 into 0
+        self.pair(code, value + 0.0)
+    }
+
+    /// A point in path coordinates under the x code `code`, with y and z at +10 and +20
+    fn point(&mut self, code: u32, point: (f64, f64)) -> io::Result<()> {
+        let (x, y) = self.system.map(point);
+        self.number(code, x)?;
+        self.number(code + 10, y)?;
+        self.number(code + 20, 0.0)
+    }
+
+    fn entity(&mut self, kind: &str, layer: &str, subclass: &str) -> io::Result<()> {
+        self.pair(0, kind)?;
+        self.handle(5, self.owner)?;
+        self.pair(100, "AcDbEntity")?;
+        self.pair(8, layer)?;
+        self.pair(100, subclass)
+    }
+
+    fn line(&mut self, layer: &str, start: (f64, f64), end: (f64, f64)) -> io::Result<()> {
+        self.entity("LINE", layer, "AcDbLine")?;
+        self.point(10, start)?;
+        self.point(11, end)
+    }
+
+    /// A Bézier curve as the clamped B-spline with the same control points
+    fn spline(&mut self, layer: &str, controls: &[(f64, f64)]) -> io::Result<()> {
+        let degree = controls.len() - 1;
+
+        self.entity("SPLINE", layer, "AcDbSpline")?;
+        // planar
+        self.pair(70, 8)?;
+        self.pair(71, degree)?;
+        self.pair(72, 2 * controls.len())?;
+        self.pair(73, controls.len())?;
+        self.pair(74, 0)?;
+        for knot in 0..2 * controls.len() {
+            self.pair(40, if knot < controls.len() { "0.0" } else { "1.0" })?;
+        }
+        for &control in controls {
+            self.point(10, control)?;
+        }
+
+        Ok(())
+    }
+
+    /// An arc as ARC when circular and ELLIPSE otherwise, both running counterclockwise in
+    /// the drawing from their start to their end
+    fn arc(&mut self, layer: &str, segment: &Segment) -> io::Result<()> {
+        let Some(arc) = segment.arc_center() else {
+            return self.line(layer, segment.start(), segment.end());
+        };
+
+        let center = self.system.map(arc.center);
+        let axis = |angle: f64| {
+            let point = self.system.map(arc.point_at_angle(angle));
+            (point.0 - center.0, point.1 - center.1)
+        };
+        let (first, second) = (axis(0.0), axis(PI / 2.0));
+
+        // mirroring or not, the arc runs counterclockwise in the drawing when the angle
+        // grows and its axes turn counterclockwise, or neither
+        let turning = (first.0 * second.1 - first.1 * second.0).signum();
+        let counterclockwise = (arc.sweep_angle > 0.0) == (turning > 0.0);
+
+        let (rx, ry) = arc.radii;
+        if (rx - ry).abs() <= 1e-9 * rx {
+            let angle = |point: (f64, f64)| {
+                let (x, y) = self.system.map(point);
+                (y - center.1)
+                    .atan2(x - center.0)
+                    .to_degrees()
+                    .rem_euclid(360.0)
+            };
+            let (start, end) = (angle(segment.start()), angle(segment.end()));
+            let (from, to) = if counterclockwise {
+                (start, end)
+            } else {
+                (end, start)
+            };
+
+            self.entity("ARC", layer, "AcDbCircle")?;
+            self.point(10, arc.center)?;
+            self.number(40, rx * self.system.scale.abs())?;
+            self.pair(100, "AcDbArc")?;
+            self.number(50, from)?;
+            return self.number(51, to);
+        }
+
+        // the point at angle θ is center + cos s·major + sin s·minor with s = θ + shift,
+        // and DXF measures its parameter p = ±s counterclockwise from the major axis
+        let (major, minor, shift) = if rx >= ry {
+            (first, second, 0.0)
+        } else {
+            (second, (-first.0, -first.1), -PI / 2.0)
+        };
+        let ratio = minor.0.hypot(minor.1) / major.0.hypot(major.1);
+        let parameter = |angle: f64| turning * (angle + shift);
+        let start = parameter(arc.start_angle);
+        let end = parameter(arc.start_angle + arc.sweep_angle);
+        let (from, to) = if counterclockwise {
+            (start, end)
+        } else {
+            (end, start)
+        };
+
+        self.entity("ELLIPSE", layer, "AcDbEllipse")?;
+        self.point(10, arc.center)?;
+        self.number(11, major.0)?;
+        self.number(21, major.1)?;
+        self.number(31, 0.0)?;
+        self.number(40, ratio)?;
+        self.number(41, from.rem_euclid(2.0 * PI))?;
+        self.number(42, to.rem_euclid(2.0 * PI))
+    }
+
+    fn polyline(&mut self, layer: &str, points: &[(f64, f64)], closed: bool) -> io::Result<()> {
+        self.entity("LWPOLYLINE", layer, "AcDbPolyline")?;
+        self.pair(90, points.len())?;
+        self.pair(70, if closed { 1 } else { 0 })?;
+        for &point in points {
+            let (x, y) = self.system.map(point);
+            self.number(10, x)?;
+            self.number(20, y)?;
+        }
+
+        Ok(())
+    }
+
+    fn path(&mut self, layer: &str, path: &Path, flatten: Option<f64>) -> io::Result<()> {
+        for subpath in path.subpaths() {
+            if subpath.segments.is_empty() {
+                continue;
+            }
+
+            if let Some(tolerance) = flatten {
+                let mut points = subpath.flatten(tolerance);
+                // the closed flag draws the closing edge
+                if subpath.closed && points.len() > 2 && points[0] == points[points.len() - 1] {
+                    points.pop();
+                }
+                self.polyline(layer, &points, subpath.closed)?;
+                continue;
+            }
+
+            for segment in &subpath.segments {
+                match *segment {
+                    Segment::Line { start, end } => self.line(layer, start, end)?,
+                    Segment::Quadratic {
+                        start,
+                        control,
+                        end,
+                    } => self.spline(layer, &[start, control, end])?,
+                    Segment::Cubic {
+                        start,
+                        control1,
+                        control2,
+                        end,
+                    } => self.spline(layer, &[start, control1, control2, end])?,
+                    Segment::Arc { .. } => self.arc(layer, segment)?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes an ASCII DXF (AutoCAD 2000) drawing with each path on its own layer. Path data
+/// carries no ids, so the layer names are given with the paths, typically the `id` of the
+/// SVG element each came from.
+pub fn write_dxf<W: Write>(
+    writer: &mut W,
+    layers: &[(&str, &Path)],
+    options: &DxfOptions,
+) -> io::Result<()> {
+    let names: Vec<String> = layers.iter().map(|(name, _)| layer_name(name)).collect();
+    let mut table = names.clone();
+    // layer 0 always exists
+    table.push("0".to_string());
+    table.sort();
+    table.dedup();
+
+    // $HANDSEED in the header has to be above every handle, so the rest goes first
+    let mut body = vec![];
+    let mut dxf = DxfWriter {
+        writer: &mut body,
+        system: options.coordinates,
+        next_handle: 1,
+        owner: 0,
+    };
+
+    let spaces = dxf.tables(&table)?;
+    dxf.blocks(spaces)?;
+
+    dxf.owner = spaces.0;
+    dxf.pair(0, "SECTION")?;
+    dxf.pair(2, "ENTITIES")?;
+    for (name, (_, path)) in names.iter().zip(layers) {
+        dxf.path(name, path, options.flatten)?;
+    }
+    dxf.pair(0, "ENDSEC")?;
+
+    dxf.objects()?;
+    let seed = dxf.next_handle;
+
+    let mut dxf = DxfWriter {
+        writer,
+        system: options.coordinates,
+        next_handle: seed,
+        owner: 0,
+    };
+
+    dxf.pair(0, "SECTION")?;
+    dxf.pair(2, "HEADER")?;
+    dxf.pair(9, "$ACADVER")?;
+    dxf.pair(1, "AC1015")?;
+    dxf.pair(9, "$HANDSEED")?;
+    dxf.pair(5, format!("{:X}", seed))?;
+    dxf.pair(0, "ENDSEC")?;
+
+    dxf.pair(0, "SECTION")?;
+    dxf.pair(2, "CLASSES")?;
+    dxf.pair(0, "ENDSEC")?;
+
+    dxf.writer.write_all(&body)?;
+    dxf.pair(0, "EOF")
+}
+
+pub fn save_dxf(filename: &str, layers: &[(&str, &Path)], options: &DxfOptions) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(filename)?);
+    write_dxf(&mut file, layers, options)?;
+    file.flush()
+}
+
+impl Path {
+    /// Writes the path alone as a DXF drawing, on `layer`
+    pub fn write_dxf<W: Write>(
+        &self,
+        writer: &mut W,
+        layer: &str,
+        options: &DxfOptions,
+    ) -> io::Result<()> {
+        write_dxf(writer, &[(layer, self)], options)
+    }
+
+    pub fn save_dxf(&self, filename: &str, layer: &str, options: &DxfOptions) -> io::Result<()> {
+        save_dxf(filename, &[(layer, self)], options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Group code and value pairs of a drawing
+    fn pairs(layers: &[(&str, &Path)], options: &DxfOptions) -> Vec<(u32, String)> {
+        let mut out = vec![];
+        write_dxf(&mut out, layers, options).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        lines
+            .chunks(2)
+            .map(|pair| (pair[0].trim().parse().unwrap(), pair[1].to_string()))
+            .collect()
+    }
+
+    /// Values of `code` right after each `(0, kind)`, e.g. the names of sections
+    fn names_after(pairs: &[(u32, String)], kind: &str, code: u32) -> Vec<String> {
+        pairs
+            .windows(2)
+            .filter(|pair| pair[0] == (0, kind.to_string()) && pair[1].0 == code)
+            .map(|pair| pair[1].1.clone())
+            .collect()
+    }
+
+    #[test]
+    fn writes_the_r2000_structure() {
+        let path = Path::init("M0 0 L10 0 Q15 5 10 10 C5 15 0 15 0 10 A5 5 0 0 1 0 0 Z");
+        let pairs = pairs(&[("outline", &path)], &DxfOptions::default());
+
+        assert_eq!(
+            names_after(&pairs, "SECTION", 2),
+            ["HEADER", "CLASSES", "TABLES", "BLOCKS", "ENTITIES", "OBJECTS"]
+        );
+        assert_eq!(
+            names_after(&pairs, "TABLE", 2),
+            [
+                "VPORT",
+                "LTYPE",
+                "LAYER",
+                "STYLE",
+                "VIEW",
+                "UCS",
+                "APPID",
+                "DIMSTYLE",
+                "BLOCK_RECORD"
+            ]
+        );
+        assert_eq!(pairs.last(), Some(&(0, "EOF".to_string())));
+
+        // every layer used has a record, and the linetype of every layer is defined
+        let layers: Vec<String> = (0..pairs.len())
+            .filter(|&i| pairs[i] == (0, "LAYER".to_string()))
+            .filter_map(|i| {
+                pairs[i + 1..]
+                    .iter()
+                    .take_while(|pair| pair.0 != 0)
+                    .find(|pair| pair.0 == 2)
+            })
+            .map(|pair| pair.1.clone())
+            .collect();
+        assert_eq!(layers, ["0", "outline"]);
+        assert!(pairs.contains(&(2, CONTINUOUS.to_string())));
+        assert!(pairs.contains(&(6, CONTINUOUS.to_string())));
+    }
+
+    #[test]
+    fn handles_are_unique_and_below_the_seed() {
+        let path = Path::init("M0 0 L10 0 Q15 5 10 10 C5 15 0 15 0 10 Z");
+        let pairs = pairs(&[("a", &path), ("b", &path)], &DxfOptions::default());
+        let hex = |value: &str| u64::from_str_radix(value, 16).unwrap();
+
+        let seed_at = pairs.iter().position(|pair| pair.1 == "$HANDSEED").unwrap();
+        let seed = hex(&pairs[seed_at + 1].1);
+
+        let mut handles: Vec<u64> = pairs[seed_at + 2..]
+            .iter()
+            .filter(|pair| pair.0 == 5 || pair.0 == 105)
+            .map(|pair| hex(&pair.1))
+            .collect();
+        let count = handles.len();
+        handles.sort();
+        handles.dedup();
+
+        assert_eq!(handles.len(), count);
+        assert!(handles.iter().all(|&handle| handle > 0 && handle < seed));
+        // owners are objects of the drawing too
+        assert!(pairs
+            .iter()
+            .filter(|pair| pair.0 == 330)
+            .all(|pair| pair.1 == "0" || handles.binary_search(&hex(&pair.1)).is_ok()));
+    }
+
+    #[test]
+    fn splines_have_two_knots_per_control_point() {
+        let path = Path::init("M0 0 Q5 10 10 0 C15 -10 20 10 25 0");
+        let pairs = pairs(&[("curves", &path)], &DxfOptions::default());
+
+        let splines: Vec<&[(u32, String)]> = pairs
+            .split(|pair| pair.0 == 0)
+            .filter(|entity| entity.contains(&(100, "AcDbSpline".to_string())))
+            .collect();
+        assert_eq!(splines.len(), 2);
+
+        for (spline, controls) in splines.iter().zip([3, 4]) {
+            let value = |code: u32| {
+                spline
+                    .iter()
+                    .find(|pair| pair.0 == code)
+                    .map(|pair| pair.1.parse::<usize>().unwrap())
+            };
+            let count = |code: u32| spline.iter().filter(|pair| pair.0 == code).count();
+
+            assert_eq!(value(71), Some(controls - 1));
+            assert_eq!(value(72), Some(2 * controls));
+            assert_eq!(value(73), Some(controls));
+            assert_eq!(count(40), 2 * controls);
+            assert_eq!(count(10), controls);
+        }
+    }
+}
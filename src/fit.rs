@@ -0,0 +1,391 @@
+use crate::segment::{distance, distance_to_line_segment};
+use crate::{CoordinateSystem, Path, Sample, Segment, Subpath};
+
+/// Halvings of a point run before a fitted cubic is accepted whatever its error
+const MAX_FIT_DEPTH: usize = 16;
+/// Newton steps that move the parameters of the points closer to a nearly good fit
+const REPARAMETERIZE_STEPS: usize = 4;
+
+/// Settings for `Path::fit_points` and `Path::fit_samples`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FitOptions {
+    /// largest distance allowed between a point and the fitted curve
+    pub tolerance: f64,
+    /// smallest turn, in degrees, between neighbouring point steps that is kept as a corner
+    /// instead of being smoothed over
+    pub corner_angle: f64,
+}
+
+impl Default for FitOptions {
+    fn default() -> Self {
+        FitOptions {
+            tolerance: 0.1,
+            corner_angle: 30.0,
+        }
+    }
+}
+
+/// `v` scaled to length 1, `fallback` when it is too short to have a direction
+pub(crate) fn unit(v: (f64, f64), fallback: (f64, f64)) -> (f64, f64) {
+    let length = v.0.hypot(v.1);
+    if length < 1e-12 {
+        fallback
+    } else {
+        (v.0 / length, v.1 / length)
+    }
+}
+
+/// Handle lengths (α, β) of the cubic that leaves `p0` along `d0` and arrives at `p3` along
+/// `d3`, fitted by least squares to samples of (parameter, point)
+pub(crate) fn least_squares_handles(
+    samples: impl IntoIterator<Item = (f64, (f64, f64))>,
+    (p0, d0): ((f64, f64), (f64, f64)),
+    (p3, d3): ((f64, f64), (f64, f64)),
+) -> (f64, f64) {
+    // residual r = α·b1·d0 - β·b2·d3, solved for α and β by the normal equations
+    let (mut c00, mut c01, mut c11, mut x0, mut x1) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for (u, point) in samples {
+        let mu = 1.0 - u;
+        let (b0, b1, b2, b3) = (mu * mu * mu, 3.0 * mu * mu * u, 3.0 * mu * u * u, u * u * u);
+        let a1 = (b1 * d0.0, b1 * d0.1);
+        let a2 = (-b2 * d3.0, -b2 * d3.1);
+        let r = (
+            point.0 - (b0 + b1) * p0.0 - (b2 + b3) * p3.0,
+            point.1 - (b0 + b1) * p0.1 - (b2 + b3) * p3.1,
+        );
+
+        c00 += a1.0 * a1.0 + a1.1 * a1.1;
+        c01 += a1.0 * a2.0 + a1.1 * a2.1;
+        c11 += a2.0 * a2.0 + a2.1 * a2.1;
+        x0 += a1.0 * r.0 + a1.1 * r.1;
+        x1 += a2.0 * r.0 + a2.1 * r.1;
+    }
+
+    // handles that come out negative or tiny make loops and cusps, so fall back to a third
+    // of the span as Schneider does
+    let span = distance(p0, p3);
+    let determinant = c00 * c11 - c01 * c01;
+    if determinant.abs() > 1e-12 {
+        let (a, b) = (
+            (x0 * c11 - x1 * c01) / determinant,
+            (c00 * x1 - c01 * x0) / determinant,
+        );
+        if a > span * 1e-6 && b > span * 1e-6 {
+            return (a, b);
+        }
+    }
+
+    (span / 3.0, span / 3.0)
+}
+
+fn direction(from: (f64, f64), to: (f64, f64)) -> (f64, f64) {
+    unit((to.0 - from.0, to.1 - from.1), (1.0, 0.0))
+}
+
+/// Chord length parameters of the points, from 0 at the first to 1 at the last
+fn chord_parameters(points: &[(f64, f64)]) -> Vec<f64> {
+    let mut parameters = vec![0.0];
+    for pair in points.windows(2) {
+        let last = parameters[parameters.len() - 1];
+        parameters.push(last + distance(pair[0], pair[1]));
+    }
+
+    let total = parameters[parameters.len() - 1];
+    parameters.iter().map(|u| u / total).collect()
+}
+
+/// Cubic through the end points leaving along `start_tangent` and arriving along
+/// `end_tangent`, with handle lengths from least squares over the points at `parameters`
+fn least_squares_cubic(
+    points: &[(f64, f64)],
+    parameters: &[f64],
+    start_tangent: (f64, f64),
+    end_tangent: (f64, f64),
+) -> Segment {
+    let (p0, p3) = (points[0], points[points.len() - 1]);
+    let (d0, d3) = (start_tangent, end_tangent);
+
+    let (alpha, beta) = least_squares_handles(
+        parameters.iter().copied().zip(points.iter().copied()),
+        (p0, d0),
+        (p3, d3),
+    );
+
+    Segment::Cubic {
+        start: p0,
+        control1: (p0.0 + alpha * d0.0, p0.1 + alpha * d0.1),
+        control2: (p3.0 - beta * d3.0, p3.1 - beta * d3.1),
+        end: p3,
+    }
+}
+
+/// Largest distance between a point and the curve at its parameter, and where it is
+fn max_error(points: &[(f64, f64)], parameters: &[f64], cubic: &Segment) -> (f64, usize) {
+    let mut worst = (0.0, points.len() / 2);
+
+    for i in 1..points.len() - 1 {
+        let error = distance(points[i], cubic.point_at(parameters[i]));
+        if error > worst.0 {
+            worst = (error, i);
+        }
+    }
+
+    worst
+}
+
+/// One Newton step per point towards the parameter of the closest point on the curve
+fn reparameterize(points: &[(f64, f64)], parameters: &mut [f64], cubic: &Segment) {
+    for (point, u) in points.iter().zip(parameters.iter_mut()) {
+        let on_curve = cubic.point_at(*u);
+        let first = cubic.derivative(*u);
+        let second = cubic.second_derivative(*u);
+        let difference = (on_curve.0 - point.0, on_curve.1 - point.1);
+
+        let numerator = difference.0 * first.0 + difference.1 * first.1;
+        let denominator = first.0 * first.0
+            + first.1 * first.1
+            + difference.0 * second.0
+            + difference.1 * second.1;
+
+        if denominator.abs() > 1e-12 {
+            *u = (*u - numerator / denominator).clamp(0.0, 1.0);
+        }
+    }
+}
+
+/// Fits the points with cubics within `tolerance`, splitting the run at the point fitted
+/// worst until every piece is close enough. Runs that are straight within the tolerance
+/// become a single line.
+fn fit_run(
+    points: &[(f64, f64)],
+    (start_tangent, end_tangent): ((f64, f64), (f64, f64)),
+    tolerance: f64,
+    depth: usize,
+    out: &mut Vec<Segment>,
+) {
+    let (first, last) = (points[0], points[points.len() - 1]);
+
+    // only whole runs between corners, so lines never break the smoothness of a curve
+    if depth == 0
+        && points
+            .iter()
+            .all(|point| distance_to_line_segment(*point, first, last) <= tolerance)
+    {
+        out.push(Segment::Line {
+            start: first,
+            end: last,
+        });
+        return;
+    }
+
+    let mut parameters = chord_parameters(points);
+    let mut cubic = least_squares_cubic(points, &parameters, start_tangent, end_tangent);
+    let (mut error, mut split) = max_error(points, &parameters, &cubic);
+
+    // a fit that is only a little off usually gets there once the parameters settle
+    if error > tolerance && error < tolerance * 4.0 {
+        for _ in 0..REPARAMETERIZE_STEPS {
+            reparameterize(points, &mut parameters, &cubic);
+            cubic = least_squares_cubic(points, &parameters, start_tangent, end_tangent);
+            (error, split) = max_error(points, &parameters, &cubic);
+            if error <= tolerance {
+                break;
+            }
+        }
+    }
+
+    if error <= tolerance || depth >= MAX_FIT_DEPTH || points.len() < 3 {
+        out.push(cubic);
+        return;
+    }
+
+    // the curve stays smooth through the split, with the direction of the neighbouring points
+    let split = split.clamp(1, points.len() - 2);
+    let tangent = direction(points[split - 1], points[split + 1]);
+    fit_run(
+        &points[..=split],
+        (start_tangent, tangent),
+        tolerance,
+        depth + 1,
+        out,
+    );
+    fit_run(
+        &points[split..],
+        (tangent, end_tangent),
+        tolerance,
+        depth + 1,
+        out,
+    );
+}
+
+/// Fits one sequence of points with lines and cubics. The sequence is closed when its
+/// first and last points coincide; corners sharper than `corner_angle` are kept sharp.
+pub(crate) fn fit_subpath(points: &[(f64, f64)], options: &FitOptions) -> Option<Subpath> {
+    // repeated points have no direction and would only upset the tangents
+    let mut points: Vec<(f64, f64)> = points.to_vec();
+    points.dedup_by(|b, a| distance(*a, *b) < 1e-12);
+
+    let first = *points.first()?;
+    if points.len() == 1 {
+        return Some(Subpath {
+            start: first,
+            segments: vec![],
+            closed: false,
+        });
+    }
+
+    let closed = points.len() > 2 && distance(first, points[points.len() - 1]) < 1e-9;
+    if closed {
+        points.pop();
+    }
+
+    let count = points.len();
+    let neighbours = |i: usize| {
+        if closed {
+            (points[(i + count - 1) % count], points[(i + 1) % count])
+        } else {
+            (points[i.saturating_sub(1)], points[(i + 1).min(count - 1)])
+        }
+    };
+
+    let limit = options.corner_angle.to_radians().cos();
+    let is_corner = |i: usize| {
+        let (before, after) = neighbours(i);
+        let (incoming, outgoing) = (direction(before, points[i]), direction(points[i], after));
+        incoming.0 * outgoing.0 + incoming.1 * outgoing.1 < limit
+    };
+
+    let interior = if closed { 0..count } else { 1..count - 1 };
+    let mut corners: Vec<usize> = interior.filter(|i| is_corner(*i)).collect();
+
+    // a closed loop is cut open at a corner if it has one, and runs round to where it started
+    let rotation = if closed {
+        corners.first().copied().unwrap_or(0)
+    } else {
+        0
+    };
+    if closed {
+        points.rotate_left(rotation);
+        points.push(points[0]);
+        for corner in corners.iter_mut() {
+            *corner = (*corner + count - rotation) % count;
+        }
+    }
+
+    let mut breaks = vec![0];
+    breaks.extend(corners.iter().filter(|i| **i > 0));
+    breaks.push(points.len() - 1);
+
+    let mut segments = vec![];
+    for pair in breaks.windows(2) {
+        let run = &points[pair[0]..=pair[1]];
+
+        // corners get the direction of the step next to them, smooth ends the centered one
+        let start_tangent = if pair[0] == 0 && closed && corners.is_empty() {
+            direction(points[points.len() - 2], points[1])
+        } else {
+            direction(run[0], run[1])
+        };
+        let end_tangent = if pair[1] == points.len() - 1 && closed && corners.is_empty() {
+            direction(points[points.len() - 2], points[1])
+        } else {
+            direction(run[run.len() - 2], run[run.len() - 1])
+        };
+
+        fit_run(
+            run,
+            (start_tangent, end_tangent),
+            options.tolerance,
+            0,
+            &mut segments,
+        );
+    }
+
+    Some(Subpath {
+        start: points[0],
+        segments,
+        closed,
+    })
+}
+
+impl Path {
+    /// Path of lines and cubics that passes within `options.tolerance` of every point, in
+    /// path coordinates. Fitting follows Schneider: handle lengths come from least squares,
+    /// and pieces that are off by too much are split where they are worst. The result is
+    /// closed when the first and last point coincide.
+    pub fn fit_points(points: &[(f64, f64)], options: &FitOptions) -> Path {
+        let subpaths: Vec<Subpath> = fit_subpath(points, options).into_iter().collect();
+        Path::from_subpaths(&subpaths)
+    }
+
+    /// Path fitted to samples as returned by `samples`, one subpath per sampled subpath.
+    /// Samples are in cartesian coordinates, so they are flipped back into path coordinates
+    /// first, and a sampled closed subpath comes back closed.
+    pub fn fit_samples(samples: &[Sample], options: &FitOptions) -> Path {
+        let subpaths: Vec<Subpath> = samples
+            .chunk_by(|a, b| a.subpath == b.subpath)
+            .filter_map(|run| {
+                let points: Vec<(f64, f64)> = run
+                    .iter()
+                    .map(|sample| CoordinateSystem::CARTESIAN.unmap(sample.point))
+                    .collect();
+                fit_subpath(&points, options)
+            })
+            .collect();
+
+        Path::from_subpaths(&subpaths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn fits_points_sampled_from_a_circle() {
+        let points: Vec<(f64, f64)> = (0..=100)
+            .map(|i| {
+                let angle = 2.0 * PI * i as f64 / 100.0;
+                (10.0 * angle.cos(), 10.0 * angle.sin())
+            })
+            .collect();
+
+        // a circle takes four cubics to within 0.03 %
+        for (tolerance, most_segments) in [(0.1, 4), (0.001, 16)] {
+            let options = FitOptions {
+                tolerance,
+                ..FitOptions::default()
+            };
+            let path = Path::fit_points(&points, &options);
+            let subpaths = path.subpaths();
+
+            assert_eq!(subpaths.len(), 1);
+            assert!(subpaths[0].closed);
+            assert!(subpaths[0].segments.len() <= most_segments);
+            for &point in &points {
+                let distance = path.distance_to(point).unwrap();
+                assert!(distance <= tolerance, "{:?} is {} away", point, distance);
+            }
+        }
+    }
+
+    #[test]
+    fn keeps_corners() {
+        let points: Vec<(f64, f64)> = (0..=20)
+            .map(|i| {
+                if i <= 10 {
+                    (i as f64, 0.0)
+                } else {
+                    (10.0, (i - 10) as f64)
+                }
+            })
+            .collect();
+        let path = Path::fit_points(&points, &FitOptions::default());
+        let segments = path.segments();
+
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].end(), (10.0, 0.0));
+    }
+}
@@ -0,0 +1,255 @@
+use crate::{CoordinateSystem, Path, Segment};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+/// How the tool is lifted off the work and lowered onto it
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PenControl {
+    /// move Z to `down` to draw and back to the safe height to travel
+    Z { down: f64 },
+    /// `M3 S<power>` to draw and `M5` to travel, as servo pen plotters expect
+    Servo { power: f64 },
+}
+
+/// Unit the machine reads coordinates and feeds in, `G21` or `G20`. Choosing one does not
+/// convert anything, `GcodeOptions::scale` does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GcodeUnits {
+    Millimeters,
+    Inches,
+}
+
+/// Settings for `Path::write_gcode`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GcodeOptions {
+    pub pen: PenControl,
+    /// feed for drawing moves, in units per minute
+    pub feed_rate: f64,
+    /// feed for lowering the pen with `PenControl::Z`
+    pub plunge_rate: f64,
+    pub units: GcodeUnits,
+    /// machine units per path unit: 1 when the path is drawn in `units` already, 1 / 25.4
+    /// for a path in millimetres on a machine set to inches
+    pub scale: f64,
+    /// added to every machine coordinate, in machine units
+    pub origin: (f64, f64),
+    /// Z height for travel moves and at the start and end of the program
+    pub safe_height: f64,
+    /// write circular arcs as `G2`/`G3` instead of flattening them
+    pub arcs: bool,
+    /// largest distance between the flattened moves and the true curve, in path units
+    pub tolerance: f64,
+    /// decimal places of coordinates and feeds
+    pub precision: usize,
+}
+
+impl Default for GcodeOptions {
+    fn default() -> Self {
+        GcodeOptions {
+            pen: PenControl::Z { down: 0.0 },
+            feed_rate: 1000.0,
+            plunge_rate: 300.0,
+            units: GcodeUnits::Millimeters,
+            scale: 1.0,
+            origin: (0.0, 0.0),
+            safe_height: 5.0,
+            arcs: false,
+            tolerance: 0.01,
+            precision: 3,
+        }
+    }
+}
+
+/// Writes G-code words with a fixed number of decimals
+struct GcodeWriter<'a, W: Write> {
+    writer: &'a mut W,
+    options: &'a GcodeOptions,
+}
+
+impl<W: Write> GcodeWriter<'_, W> {
+    fn number(&self, value: f64) -> String {
+        // adding zero turns -0 into 0
+        format!("{:.*}", self.options.precision, value + 0.0)
+    }
+
+    /// Machine coordinates of a point in path coordinates: y up, scaled and shifted by the
+    /// origin
+    fn machine(&self, point: (f64, f64)) -> (f64, f64) {
+        CoordinateSystem {
+            scale: self.options.scale,
+            flip_y: true,
+            origin: self.options.origin,
+        }
+        .map(point)
+    }
+
+    fn line(&mut self, words: &str) -> io::Result<()> {
+        writeln!(self.writer, "{}", words)
+    }
+
+    fn travel(&mut self, point: (f64, f64)) -> io::Result<()> {
+        let (x, y) = self.machine(point);
+        let words = format!("G0 X{} Y{}", self.number(x), self.number(y));
+        self.line(&words)
+    }
+
+    fn feed(&mut self, point: (f64, f64)) -> io::Result<()> {
+        let (x, y) = self.machine(point);
+        let words = format!("G1 X{} Y{}", self.number(x), self.number(y));
+        self.line(&words)
+    }
+
+    fn pen_up(&mut self) -> io::Result<()> {
+        match self.options.pen {
+            PenControl::Z { .. } => {
+                let words = format!("G0 Z{}", self.number(self.options.safe_height));
+                self.line(&words)
+            }
+            PenControl::Servo { .. } => self.line("M5"),
+        }
+    }
+
+    fn pen_down(&mut self) -> io::Result<()> {
+        match self.options.pen {
+            PenControl::Z { down } => {
+                let words = format!(
+                    "G1 Z{} F{}",
+                    self.number(down),
+                    self.number(self.options.plunge_rate)
+                );
+                self.line(&words)?;
+            }
+            PenControl::Servo { power } => {
+                let words = format!("M3 S{}", self.number(power));
+                self.line(&words)?;
+            }
+        }
+
+        // feed is modal, so it is set again after a Z plunge changed it
+        let words = format!("G1 F{}", self.number(self.options.feed_rate));
+        self.line(&words)
+    }
+
+    /// A circular arc as a single `G2`/`G3`, or `false` if the segment is not one
+    fn arc(&mut self, segment: &Segment) -> io::Result<bool> {
+        let Some(arc) = segment.arc_center() else {
+            return Ok(false);
+        };
+        let (rx, ry) = arc.radii;
+        if (rx - ry).abs() > 1e-9 * rx {
+            return Ok(false);
+        }
+
+        // an increasing angle turns clockwise on screen in both coordinate systems
+        let command = if arc.sweep_angle > 0.0 { "G2" } else { "G3" };
+        let (x, y) = self.machine(segment.end());
+        let start = self.machine(segment.start());
+        let center = self.machine(arc.center);
+        let words = format!(
+            "{} X{} Y{} I{} J{}",
+            command,
+            self.number(x),
+            self.number(y),
+            self.number(center.0 - start.0),
+            self.number(center.1 - start.1)
+        );
+        self.line(&words)?;
+        Ok(true)
+    }
+}
+
+impl Path {
+    /// Writes G-code that draws every subpath with the pen down and travels between them
+    /// with it up. Coordinates are flipped to y up like `points`, scaled into machine units
+    /// and shifted by the origin; curves are flattened to `G1` moves unless `arcs` allows
+    /// `G2`/`G3` for circular arcs.
+    pub fn write_gcode<W: Write>(&self, writer: &mut W, options: &GcodeOptions) -> io::Result<()> {
+        let mut gcode = GcodeWriter { writer, options };
+
+        gcode.line(match options.units {
+            GcodeUnits::Millimeters => "G21",
+            GcodeUnits::Inches => "G20",
+        })?;
+        gcode.line("G90")?;
+        gcode.pen_up()?;
+
+        for subpath in self.subpaths() {
+            if subpath.segments.is_empty() {
+                continue;
+            }
+
+            gcode.travel(subpath.start)?;
+            gcode.pen_down()?;
+
+            for segment in &subpath.segments {
+                if options.arcs && gcode.arc(segment)? {
+                    continue;
+                }
+                for point in segment.flatten(options.tolerance).into_iter().skip(1) {
+                    gcode.feed(point)?;
+                }
+            }
+
+            gcode.pen_up()?;
+        }
+
+        gcode.line("M2")
+    }
+
+    pub fn save_gcode(&self, filename: &str, options: &GcodeOptions) -> io::Result<()> {
+        let mut file = BufWriter::new(File::create(filename)?);
+        self.write_gcode(&mut file, options)?;
+        file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gcode(data: &str, options: &GcodeOptions) -> String {
+        let mut out = vec![];
+        Path::init(data).write_gcode(&mut out, options).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn arcs_keep_their_direction_under_the_flip() {
+        // clockwise on screen through (15, 5), then counterclockwise through (5, 15); y up
+        // turns the drawing over but keeps clockwise clockwise
+        let options = GcodeOptions {
+            arcs: true,
+            precision: 1,
+            ..GcodeOptions::default()
+        };
+        assert_eq!(
+            gcode("M0 0 H10 A5 5 0 0 1 10 10 A5 5 0 0 0 10 20", &options),
+            "G21\nG90\nG0 Z5.0\n\
+             G0 X0.0 Y0.0\nG1 Z0.0 F300.0\nG1 F1000.0\n\
+             G1 X10.0 Y0.0\nG2 X10.0 Y-10.0 I0.0 J-5.0\nG3 X10.0 Y-20.0 I0.0 J-5.0\n\
+             G0 Z5.0\nM2\n"
+        );
+    }
+
+    #[test]
+    fn scales_into_machine_units() {
+        let options = GcodeOptions {
+            pen: PenControl::Servo { power: 1000.0 },
+            units: GcodeUnits::Inches,
+            scale: 1.0 / 25.4,
+            origin: (1.0, 2.0),
+            feed_rate: 40.0,
+            precision: 2,
+            ..GcodeOptions::default()
+        };
+        assert_eq!(
+            gcode("M0 0 H25.4", &options),
+            "G20\nG90\nM5\n\
+             G0 X1.00 Y2.00\nM3 S1000.00\nG1 F40.00\nG1 X2.00 Y2.00\nM5\n\
+             M2\n"
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{CoordinateSystem, Path};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+/// Settings for `Path::write_hpgl`. Path units are taken to be millimetres.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HpglOptions {
+    /// plotter units per millimetre, 40 on most HPGL devices
+    pub units_per_mm: f64,
+    /// move the origin from the top left of the drawing, where SVG has it, to the bottom
+    /// left with y pointing up, where plotters have it
+    pub flip_origin: bool,
+    /// pen selected with `SP`
+    pub pen: u32,
+    /// largest distance between the plotted lines and the true curve, in path units
+    pub tolerance: f64,
+}
+
+impl Default for HpglOptions {
+    fn default() -> Self {
+        HpglOptions {
+            units_per_mm: 40.0,
+            flip_origin: true,
+            pen: 1,
+            tolerance: 0.025,
+        }
+    }
+}
+
+impl Path {
+    /// Coordinate system of `write_hpgl`: plotter units, and with `flip_origin` mirrored
+    /// so the bottom of the drawing lies on the x axis
+    pub fn hpgl_coordinates(&self, options: &HpglOptions) -> CoordinateSystem {
+        let bottom = self.bounding_box().map_or(0.0, |(_, max)| max.1);
+
+        CoordinateSystem {
+            scale: options.units_per_mm,
+            flip_y: options.flip_origin,
+            origin: if options.flip_origin {
+                (0.0, bottom * options.units_per_mm)
+            } else {
+                (0.0, 0.0)
+            },
+        }
+    }
+
+    /// Writes HPGL that plots every subpath, flattened, with the pen down and lifts it in
+    /// between. Coordinates are rounded to whole plotter units.
+    pub fn write_hpgl<W: Write>(&self, writer: &mut W, options: &HpglOptions) -> io::Result<()> {
+        let system = self.hpgl_coordinates(options);
+        let plotter = |point| {
+            let (x, y) = system.map(point);
+            (x.round() as i64, y.round() as i64)
+        };
+
+        writeln!(writer, "IN;SP{};", options.pen)?;
+
+        for subpath in self.subpaths() {
+            if subpath.segments.is_empty() {
+                continue;
+            }
+
+            let mut points: Vec<(i64, i64)> = subpath
+                .flatten(options.tolerance)
+                .into_iter()
+                .map(plotter)
+                .collect();
+            points.dedup();
+
+            let (x, y) = points[0];
+            writeln!(writer, "PU{},{};", x, y)?;
+
+            // a subpath shorter than a plotter unit still leaves a dot
+            let moves: Vec<String> = match points.len() {
+                1 => vec![format!("{},{}", x, y)],
+                _ => points[1..]
+                    .iter()
+                    .map(|(x, y)| format!("{},{}", x, y))
+                    .collect(),
+            };
+            writeln!(writer, "PD{};", moves.join(","))?;
+        }
+
+        writeln!(writer, "PU;SP0;")
+    }
+
+    pub fn save_hpgl(&self, filename: &str, options: &HpglOptions) -> io::Result<()> {
+        let mut file = BufWriter::new(File::create(filename)?);
+        self.write_hpgl(&mut file, options)?;
+        file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hpgl(data: &str, options: &HpglOptions) -> String {
+        let mut out = vec![];
+        Path::init(data).write_hpgl(&mut out, options).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn moves_the_origin_to_the_bottom_left() {
+        // 40 plotter units per millimetre, the drawing is 5 mm high
+        assert_eq!(
+            hpgl("M0 0 H10 V5", &HpglOptions::default()),
+            "IN;SP1;\nPU0,200;\nPD400,200,400,0;\nPU;SP0;\n"
+        );
+
+        let unflipped = HpglOptions {
+            flip_origin: false,
+            pen: 2,
+            ..HpglOptions::default()
+        };
+        assert_eq!(
+            hpgl("M0 0 H10 V5", &unflipped),
+            "IN;SP2;\nPU0,0;\nPD400,0,400,200;\nPU;SP0;\n"
+        );
+    }
+
+    #[test]
+    fn tiny_subpaths_leave_a_dot() {
+        assert_eq!(
+            hpgl("M1 1 H1.001 M2 1 H3", &HpglOptions::default()),
+            "IN;SP1;\nPU40,0;\nPD40,0;\nPU80,0;\nPD120,0;\nPU;SP0;\n"
+        );
+    }
+}
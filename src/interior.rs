@@ -0,0 +1,288 @@
+use crate::segment::distance;
+use crate::{CoordinateSystem, FillRule, Path};
+use rand::rngs::StdRng;
+use rand::seq::IteratorRandom;
+use rand::{Rng, SeedableRng};
+use std::fmt;
+
+/// Candidates tried around each active point in Bridson's algorithm
+const POISSON_ATTEMPTS: usize = 30;
+
+/// Most cells of the Poisson-disk background grid or of a sampling grid, about 4 million
+const MAX_GRID_CELLS: f64 = (1 << 22) as f64;
+
+/// How points inside a filled region are placed
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InteriorSampling {
+    /// `count` independent uniformly distributed points
+    Uniform { count: usize },
+    /// blue noise: no two points closer than `min_distance`, gaps no wider than twice that
+    PoissonDisk { min_distance: f64 },
+    /// square grid with the given spacing
+    Grid { spacing: f64 },
+}
+
+/// Why `interior_samples` could not place points
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InteriorSamplingError {
+    /// `min_distance` or `spacing` is not a positive, finite number
+    InvalidSpacing(f64),
+    /// the spacing is so small for the size of the path that its grid would need this many
+    /// cells, more than the about 4 million allowed
+    TooManyCells(f64),
+    /// `Uniform` sampling gave up after a thousand tries per point with only this many
+    /// points inside, as happens for regions with little or no area
+    TooFewPoints(usize),
+}
+
+impl fmt::Display for InteriorSamplingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InteriorSamplingError::InvalidSpacing(spacing) => {
+                write!(f, "spacing {} is not a positive number", spacing)
+            }
+            InteriorSamplingError::TooManyCells(cells) => {
+                write!(f, "sampling grid of {} cells is too large", cells)
+            }
+            InteriorSamplingError::TooFewPoints(found) => {
+                write!(f, "only {} points found inside the region", found)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InteriorSamplingError {}
+
+/// Checks that a grid of `spacing` over a rectangle of `size` is small enough to allocate
+/// and walk, and returns its number of columns and rows
+fn grid_size(size: (f64, f64), spacing: f64) -> Result<(usize, usize), InteriorSamplingError> {
+    if !(spacing.is_finite() && spacing > 0.0) {
+        return Err(InteriorSamplingError::InvalidSpacing(spacing));
+    }
+
+    let columns = (size.0 / spacing).ceil().max(1.0);
+    let rows = (size.1 / spacing).ceil().max(1.0);
+    if columns * rows > MAX_GRID_CELLS {
+        return Err(InteriorSamplingError::TooManyCells(columns * rows));
+    }
+
+    Ok((columns as usize, rows as usize))
+}
+
+impl Path {
+    /// Points inside the filled path, in the cartesian system of `points` and `samples`.
+    /// Open subpaths are closed implicitly, as when filling. `seed` makes the random modes
+    /// reproducible and is ignored by `Grid`. Fails for a `min_distance` or `spacing` that
+    /// is not positive, or so small that the grid behind it would not fit in memory, and
+    /// when `Uniform` cannot find `count` points inside.
+    pub fn interior_samples(
+        &self,
+        sampling: InteriorSampling,
+        rule: FillRule,
+        seed: u64,
+    ) -> Result<Vec<(f64, f64)>, InteriorSamplingError> {
+        let Some((min, max)) = self.bounding_box() else {
+            return Ok(vec![]);
+        };
+
+        let size = (max.0 - min.0, max.1 - min.1);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let random_point = |rng: &mut StdRng| {
+            (
+                min.0 + rng.random::<f64>() * size.0,
+                min.1 + rng.random::<f64>() * size.1,
+            )
+        };
+
+        let points = match sampling {
+            InteriorSampling::Uniform { count } => {
+                let mut points = vec![];
+
+                // rejection sampling in the bounding box, give up on regions without area
+                let mut attempts = 0;
+                while points.len() < count && attempts < count.max(1) * 1000 {
+                    let point = random_point(&mut rng);
+                    if self.contains(point, rule) {
+                        points.push(point);
+                    }
+                    attempts += 1;
+                }
+
+                if points.len() < count {
+                    return Err(InteriorSamplingError::TooFewPoints(points.len()));
+                }
+                points
+            }
+            InteriorSampling::PoissonDisk { min_distance } => {
+                // Bridson's algorithm over the whole bounding box, so disconnected parts of the
+                // region are covered too, keeping only the points that are inside
+                poisson_disk(min, size, min_distance, &mut rng)?
+                    .into_iter()
+                    .filter(|&point| self.contains(point, rule))
+                    .collect()
+            }
+            InteriorSampling::Grid { spacing } => {
+                grid_size(size, spacing)?;
+                let columns = (size.0 / spacing).floor() as usize;
+                let rows = (size.1 / spacing).floor() as usize;
+
+                // center the grid in the bounding box
+                let offset = (
+                    min.0 + (size.0 - columns as f64 * spacing) / 2.0,
+                    min.1 + (size.1 - rows as f64 * spacing) / 2.0,
+                );
+
+                let mut points = vec![];
+                for row in 0..=rows {
+                    for column in 0..=columns {
+                        let point = (
+                            offset.0 + column as f64 * spacing,
+                            offset.1 + row as f64 * spacing,
+                        );
+                        if self.contains(point, rule) {
+                            points.push(point);
+                        }
+                    }
+                }
+
+                points
+            }
+        };
+
+        Ok(points
+            .into_iter()
+            .map(|point| CoordinateSystem::CARTESIAN.map(point))
+            .collect())
+    }
+}
+
+/// Poisson-disk points filling the rectangle at `origin` with the given `size`
+fn poisson_disk(
+    origin: (f64, f64),
+    size: (f64, f64),
+    min_distance: f64,
+    rng: &mut StdRng,
+) -> Result<Vec<(f64, f64)>, InteriorSamplingError> {
+    if !(min_distance.is_finite() && min_distance > 0.0) {
+        return Err(InteriorSamplingError::InvalidSpacing(min_distance));
+    }
+
+    // background grid with at most one point per cell
+    let cell = min_distance / 2f64.sqrt();
+    let (columns, rows) = grid_size(size, cell)?;
+    let mut grid: Vec<Option<usize>> = vec![None; columns * rows];
+
+    let cell_of = |point: (f64, f64)| {
+        let column = (((point.0 - origin.0) / cell) as usize).min(columns - 1);
+        let row = (((point.1 - origin.1) / cell) as usize).min(rows - 1);
+        (column, row)
+    };
+
+    let first = (
+        origin.0 + rng.random::<f64>() * size.0,
+        origin.1 + rng.random::<f64>() * size.1,
+    );
+    let mut points = vec![first];
+    let mut active = vec![0];
+    let (column, row) = cell_of(first);
+    grid[row * columns + column] = Some(0);
+
+    while !active.is_empty() {
+        let slot = (0..active.len()).choose(rng).expect("active is not empty");
+        let center = points[active[slot]];
+        let mut found = false;
+
+        for _ in 0..POISSON_ATTEMPTS {
+            // uniform in the annulus between r and 2r
+            let angle = rng.random::<f64>() * std::f64::consts::TAU;
+            let radius = min_distance * (1.0 + 3.0 * rng.random::<f64>()).sqrt();
+            let candidate = (
+                center.0 + radius * angle.cos(),
+                center.1 + radius * angle.sin(),
+            );
+
+            if candidate.0 < origin.0
+                || candidate.1 < origin.1
+                || candidate.0 > origin.0 + size.0
+                || candidate.1 > origin.1 + size.1
+            {
+                continue;
+            }
+
+            let (column, row) = cell_of(candidate);
+            let too_close = (row.saturating_sub(2)..(row + 3).min(rows)).any(|r| {
+                (column.saturating_sub(2)..(column + 3).min(columns)).any(|c| {
+                    grid[r * columns + c]
+                        .is_some_and(|index| distance(points[index], candidate) < min_distance)
+                })
+            });
+
+            if !too_close {
+                grid[row * columns + column] = Some(points.len());
+                active.push(points.len());
+                points.push(candidate);
+                found = true;
+                break;
+            }
+        }
+
+        if !found {
+            active.swap_remove(slot);
+        }
+    }
+
+    Ok(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: &str = "M0 0 H10 V10 H0 Z";
+
+    #[test]
+    fn uniform_points_are_inside() {
+        let path = Path::init(SQUARE);
+        let points = path
+            .interior_samples(
+                InteriorSampling::Uniform { count: 50 },
+                FillRule::NonZero,
+                3,
+            )
+            .unwrap();
+
+        assert_eq!(points.len(), 50);
+        // cartesian, so the square lies below the x axis
+        assert!(points
+            .iter()
+            .all(|&(x, y)| (0.0..=10.0).contains(&x) && (-10.0..=0.0).contains(&y)));
+    }
+
+    #[test]
+    fn uniform_reports_regions_without_area() {
+        let path = Path::init("M0 0 H10 Z");
+        let sampling = InteriorSampling::Uniform { count: 5 };
+
+        assert_eq!(
+            path.interior_samples(sampling, FillRule::NonZero, 3),
+            Err(InteriorSamplingError::TooFewPoints(0))
+        );
+    }
+
+    #[test]
+    fn grids_and_spacings_are_checked() {
+        let path = Path::init(SQUARE);
+        let grid = |spacing| {
+            path.interior_samples(InteriorSampling::Grid { spacing }, FillRule::NonZero, 0)
+        };
+
+        // 1, 5 and 9 along both sides, centred in the square
+        assert_eq!(grid(4.0).map(|points| points.len()), Ok(9));
+        assert_eq!(grid(0.0), Err(InteriorSamplingError::InvalidSpacing(0.0)));
+        assert!(matches!(
+            grid(1e-6),
+            Err(InteriorSamplingError::TooManyCells(_))
+        ));
+    }
+}
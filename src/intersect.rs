@@ -0,0 +1,527 @@
+use crate::segment::distance;
+use crate::{Path, Segment, Subpath};
+
+/// Box pairs examined per segment pair before giving up, guards against overlapping curves
+const WORK_LIMIT: usize = 50_000;
+
+/// A position on a path
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PathLocation {
+    /// index into `Path::subpaths`
+    pub subpath: usize,
+    /// index into the segments of that subpath
+    pub segment: usize,
+    pub t: f64,
+}
+
+/// A point where two segments meet, with its location on both of them
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Intersection {
+    pub point: (f64, f64),
+    pub a: PathLocation,
+    pub b: PathLocation,
+}
+
+/// A segment together with the parameters of its coordinate extrema, so the bounding box
+/// of any parameter range can be computed exactly
+struct Curve {
+    segment: Segment,
+    extrema: Vec<f64>,
+}
+
+impl Curve {
+    fn new(segment: &Segment) -> Self {
+        let mut extrema = segment.extrema(0);
+        extrema.extend(segment.extrema(1));
+
+        Curve {
+            segment: *segment,
+            extrema,
+        }
+    }
+
+    fn bounding_box(&self, range: (f64, f64)) -> ((f64, f64), (f64, f64)) {
+        self.segment
+            .bounding_box_between(range.0, range.1, &self.extrema)
+    }
+}
+
+fn boxes_overlap(a: ((f64, f64), (f64, f64)), b: ((f64, f64), (f64, f64)), slack: f64) -> bool {
+    a.0 .0 <= b.1 .0 + slack
+        && b.0 .0 <= a.1 .0 + slack
+        && a.0 .1 <= b.1 .1 + slack
+        && b.0 .1 <= a.1 .1 + slack
+}
+
+fn box_size(b: ((f64, f64), (f64, f64))) -> f64 {
+    (b.1 .0 - b.0 .0).max(b.1 .1 - b.0 .1)
+}
+
+/// Distance within which a hit counts as being on an endpoint of either segment
+fn snap_distance(a: &Segment, b: &Segment) -> f64 {
+    let scale = box_size(a.bounding_box()).max(box_size(b.bounding_box()));
+    scale.max(1.0) * 1e-6
+}
+
+/// Recursively halves the larger of two parameter ranges, discarding pairs whose bounding
+/// boxes are disjoint, until both are smaller than `precision`
+fn subdivide(
+    a: &Curve,
+    range_a: (f64, f64),
+    b: &Curve,
+    range_b: (f64, f64),
+    precision: f64,
+    work: &mut usize,
+    hits: &mut Vec<(f64, f64)>,
+) {
+    *work += 1;
+    if *work > WORK_LIMIT {
+        return;
+    }
+
+    let box_a = a.bounding_box(range_a);
+    let box_b = b.bounding_box(range_b);
+
+    if !boxes_overlap(box_a, box_b, precision) {
+        return;
+    }
+
+    let (size_a, size_b) = (box_size(box_a), box_size(box_b));
+
+    if size_a < precision && size_b < precision {
+        hits.push(((range_a.0 + range_a.1) / 2.0, (range_b.0 + range_b.1) / 2.0));
+        return;
+    }
+
+    if size_a >= size_b {
+        let middle = (range_a.0 + range_a.1) / 2.0;
+        subdivide(a, (range_a.0, middle), b, range_b, precision, work, hits);
+        subdivide(a, (middle, range_a.1), b, range_b, precision, work, hits);
+    } else {
+        let middle = (range_b.0 + range_b.1) / 2.0;
+        subdivide(a, range_a, b, (range_b.0, middle), precision, work, hits);
+        subdivide(a, range_a, b, (middle, range_b.1), precision, work, hits);
+    }
+}
+
+/// Newton iterations on A(s) - B(u) = 0, skipped where the curves are tangent
+fn polish(a: &Segment, b: &Segment, (mut s, mut u): (f64, f64)) -> (f64, f64) {
+    for _ in 0..8 {
+        let (pa, pb) = (a.point_at(s), b.point_at(u));
+        let (fx, fy) = (pa.0 - pb.0, pa.1 - pb.1);
+        let (da, db) = (a.derivative(s), b.derivative(u));
+
+        // Jacobian [A'(s), -B'(u)]
+        let determinant = -da.0 * db.1 + da.1 * db.0;
+        if determinant.abs() < 1e-12 {
+            break;
+        }
+
+        let ds = (-fx * db.1 + fy * db.0) / determinant;
+        let du = (da.0 * fy - da.1 * fx) / determinant;
+        let (next_s, next_u) = ((s - ds).clamp(0.0, 1.0), (u - du).clamp(0.0, 1.0));
+
+        // only accept steps that bring the curves closer
+        if distance(a.point_at(next_s), b.point_at(next_u)) > fx.hypot(fy) {
+            break;
+        }
+        (s, u) = (next_s, next_u);
+    }
+
+    (s, u)
+}
+
+/// Closed form intersection of two lines, including the ends of collinear overlaps
+fn line_intersections(a: &Segment, b: &Segment) -> Vec<(f64, f64)> {
+    let (p, r) = (
+        a.start(),
+        (a.end().0 - a.start().0, a.end().1 - a.start().1),
+    );
+    let (q, s) = (
+        b.start(),
+        (b.end().0 - b.start().0, b.end().1 - b.start().1),
+    );
+    let cross = |u: (f64, f64), v: (f64, f64)| u.0 * v.1 - u.1 * v.0;
+
+    let denominator = cross(r, s);
+    let qp = (q.0 - p.0, q.1 - p.1);
+    let scale = r.0.hypot(r.1).max(s.0.hypot(s.1)).max(1e-300);
+
+    if denominator.abs() > 1e-12 * scale * scale {
+        let t = cross(qp, s) / denominator;
+        let u = cross(qp, r) / denominator;
+        let slack = 1e-12;
+
+        if (-slack..=1.0 + slack).contains(&t) && (-slack..=1.0 + slack).contains(&u) {
+            return vec![(t.clamp(0.0, 1.0), u.clamp(0.0, 1.0))];
+        }
+        return vec![];
+    }
+
+    // parallel: only collinear lines can touch, report the ends of the shared part
+    if cross(qp, r).abs() > 1e-9 * scale * scale {
+        return vec![];
+    }
+
+    let length_squared = r.0 * r.0 + r.1 * r.1;
+    if length_squared == 0.0 {
+        return vec![];
+    }
+
+    let project =
+        |point: (f64, f64)| ((point.0 - p.0) * r.0 + (point.1 - p.1) * r.1) / length_squared;
+    let (u0, u1) = (project(b.start()), project(b.end()));
+    let (low, high) = (u0.min(u1).max(0.0), u0.max(u1).min(1.0));
+
+    if low > high {
+        return vec![];
+    }
+
+    let on_b = |t: f64| if u1 != u0 { (t - u0) / (u1 - u0) } else { 0.0 };
+    let mut hits = vec![(low, on_b(low))];
+    if high > low {
+        hits.push((high, on_b(high)));
+    }
+    hits
+}
+
+impl Segment {
+    /// Parameter pairs (t on self, t on other) where the two segments meet
+    pub fn intersections(&self, other: &Segment) -> Vec<(f64, f64)> {
+        let snap_distance = snap_distance(self, other);
+
+        // hits on an endpoint get its exact parameter, so joints can be recognised
+        let snap = |segment: &Segment, t: f64| {
+            let point = segment.point_at(t);
+            let (to_start, to_end) = (
+                distance(point, segment.start()),
+                distance(point, segment.end()),
+            );
+
+            if to_start < snap_distance && to_start <= to_end {
+                0.0
+            } else if to_end < snap_distance {
+                1.0
+            } else {
+                t
+            }
+        };
+
+        self.intersections_between(other, (0.0, 1.0), (0.0, 1.0))
+            .into_iter()
+            .map(|(s, u)| (snap(self, s), snap(other, u)))
+            .collect()
+    }
+
+    fn intersections_between(
+        &self,
+        other: &Segment,
+        range_a: (f64, f64),
+        range_b: (f64, f64),
+    ) -> Vec<(f64, f64)> {
+        if let (Segment::Line { .. }, Segment::Line { .. }) = (self, other) {
+            return line_intersections(self, other)
+                .into_iter()
+                .filter(|(s, u)| {
+                    (range_a.0..=range_a.1).contains(s) && (range_b.0..=range_b.1).contains(u)
+                })
+                .collect();
+        }
+
+        let (a, b) = (Curve::new(self), Curve::new(other));
+        let scale = box_size(a.bounding_box(range_a)).max(box_size(b.bounding_box(range_b)));
+        let precision = scale.max(1.0) * 1e-7;
+
+        let mut candidates = vec![];
+        let mut work = 0;
+        subdivide(
+            &a,
+            range_a,
+            &b,
+            range_b,
+            precision,
+            &mut work,
+            &mut candidates,
+        );
+
+        // neighbouring boxes report the same crossing, keep one per point
+        let mut hits: Vec<(f64, f64)> = vec![];
+        for candidate in candidates {
+            let (s, u) = polish(self, other, candidate);
+            let point = self.point_at(s);
+
+            if distance(point, other.point_at(u)) > precision * 10.0 {
+                continue;
+            }
+            if hits
+                .iter()
+                .all(|&(s2, _)| distance(self.point_at(s2), point) > precision * 10.0)
+            {
+                hits.push((s, u));
+            }
+        }
+
+        hits.sort_by(|x, y| x.0.total_cmp(&y.0));
+        hits
+    }
+
+    /// Parameter pairs (t1 < t2) where the segment crosses itself, e.g. the loop of a cubic.
+    /// The segment is cut where it turns in x or y; such pieces cannot cross themselves.
+    pub fn self_intersections(&self) -> Vec<(f64, f64)> {
+        if !matches!(self, Segment::Cubic { .. }) {
+            return vec![];
+        }
+
+        let mut cuts = vec![0.0];
+        cuts.extend(self.extrema(0));
+        cuts.extend(self.extrema(1));
+        cuts.push(1.0);
+        cuts.sort_by(f64::total_cmp);
+        cuts.dedup();
+
+        let mut hits = vec![];
+        for i in 0..cuts.len() - 1 {
+            for j in i + 1..cuts.len() - 1 {
+                for (s, u) in
+                    self.intersections_between(self, (cuts[i], cuts[i + 1]), (cuts[j], cuts[j + 1]))
+                {
+                    // neighbouring pieces always meet at their shared cut
+                    if (u - s).abs() > 1e-6 {
+                        hits.push((s.min(u), s.max(u)));
+                    }
+                }
+            }
+        }
+
+        hits
+    }
+}
+
+/// Every segment with its subpath and segment index
+fn indexed_segments(subpaths: &[Subpath]) -> Vec<(usize, usize, Segment)> {
+    subpaths
+        .iter()
+        .enumerate()
+        .flat_map(|(subpath_index, subpath)| {
+            subpath
+                .segments
+                .iter()
+                .enumerate()
+                .map(move |(index, segment)| (subpath_index, index, *segment))
+        })
+        .collect()
+}
+
+/// Whether `t` is the end of a segment that another segment of the subpath continues from,
+/// in which case the same point is found again at the start of that segment
+fn continues(subpaths: &[Subpath], subpath: usize, segment: usize, t: f64) -> bool {
+    let subpath = &subpaths[subpath];
+    let last = segment + 1 == subpath.segments.len();
+
+    t == 1.0 && (!last || subpath.segments[segment].end() == subpath.start)
+}
+
+fn location(subpath: usize, segment: usize, t: f64) -> PathLocation {
+    PathLocation {
+        subpath,
+        segment,
+        t,
+    }
+}
+
+/// Intersections between two sets of subpaths, see `Path::intersections`
+pub(crate) fn intersect_subpaths(
+    subpaths: &[Subpath],
+    other_subpaths: &[Subpath],
+) -> Vec<Intersection> {
+    let other_segments = indexed_segments(other_subpaths);
+    let mut intersections = vec![];
+
+    for (subpath_a, index_a, a) in indexed_segments(subpaths) {
+        let box_a = a.bounding_box();
+
+        for (subpath_b, index_b, b) in &other_segments {
+            if !boxes_overlap(box_a, b.bounding_box(), 0.0) {
+                continue;
+            }
+
+            for (s, u) in a.intersections(b) {
+                if continues(subpaths, subpath_a, index_a, s)
+                    || continues(other_subpaths, *subpath_b, *index_b, u)
+                {
+                    continue;
+                }
+
+                intersections.push(Intersection {
+                    point: a.point_at(s),
+                    a: location(subpath_a, index_a, s),
+                    b: location(*subpath_b, *index_b, u),
+                });
+            }
+        }
+    }
+
+    intersections
+}
+
+/// Crossings of a set of subpaths with itself, see `Path::self_intersections`
+pub(crate) fn self_intersect_subpaths(subpaths: &[Subpath]) -> Vec<Intersection> {
+    let segments = indexed_segments(subpaths);
+    let mut intersections = vec![];
+
+    for (i, (subpath_a, index_a, a)) in segments.iter().enumerate() {
+        // a segment that makes up a whole subpath meets itself where the closure joins its
+        // end to its start, which is the joint between it and itself
+        let subpath = &subpaths[*subpath_a];
+        let closes_itself = subpath.segments.len() == 1 && a.end() == subpath.start;
+        let snap = snap_distance(a, a);
+
+        for (s, u) in a.self_intersections() {
+            if closes_itself && distance(a.point_at(s), a.start()) < snap {
+                continue;
+            }
+
+            intersections.push(Intersection {
+                point: a.point_at(s),
+                a: location(*subpath_a, *index_a, s),
+                b: location(*subpath_a, *index_a, u),
+            });
+        }
+
+        let box_a = a.bounding_box();
+
+        for (subpath_b, index_b, b) in &segments[i + 1..] {
+            if !boxes_overlap(box_a, b.bounding_box(), 0.0) {
+                continue;
+            }
+
+            let count = subpaths[*subpath_a].segments.len();
+            let same_subpath = subpath_a == subpath_b;
+            let next = same_subpath && *index_b == index_a + 1;
+            let wraps = same_subpath
+                && *index_a == 0
+                && *index_b == count - 1
+                && b.end() == subpaths[*subpath_a].start;
+
+            let snap_distance = snap_distance(a, b);
+
+            for (s, u) in a.intersections(b) {
+                // the joint every pair of neighbouring segments shares
+                let point = a.point_at(s);
+                let joint = (next && distance(point, a.end()) < snap_distance)
+                    || (wraps && distance(point, a.start()) < snap_distance);
+                if joint
+                    || continues(subpaths, *subpath_a, *index_a, s)
+                    || continues(subpaths, *subpath_b, *index_b, u)
+                {
+                    continue;
+                }
+
+                intersections.push(Intersection {
+                    point: a.point_at(s),
+                    a: location(*subpath_a, *index_a, s),
+                    b: location(*subpath_b, *index_b, u),
+                });
+            }
+        }
+    }
+
+    intersections
+}
+
+impl Path {
+    /// All points where this path meets `other`, `a` locating them on this path and `b` on
+    /// the other one
+    pub fn intersections(&self, other: &Path) -> Vec<Intersection> {
+        intersect_subpaths(&self.subpaths(), &other.subpaths())
+    }
+
+    /// All points where the path crosses or touches itself, apart from the joints between
+    /// consecutive segments. `a` is always the earlier location in drawing order.
+    pub fn self_intersections(&self) -> Vec<Intersection> {
+        self_intersect_subpaths(&self.subpaths())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intersections(a: &str, b: &str) -> Vec<Intersection> {
+        Path::init(a).intersections(&Path::init(b))
+    }
+
+    fn assert_near(actual: (f64, f64), expected: (f64, f64), tolerance: f64) {
+        assert!(
+            distance(actual, expected) < tolerance,
+            "{:?} instead of {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn crossing_lines() {
+        let hits = intersections("M0 0 L10 10", "M0 10 L10 0");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].point, (5.0, 5.0));
+        assert_eq!((hits[0].a.t, hits[0].b.t), (0.5, 0.5));
+    }
+
+    #[test]
+    fn tangent_curves() {
+        // touching is found once, not once per subdivision box around the point
+        let hits = intersections("M0 0 Q5 10 10 0", "M0 5 H10");
+        assert_eq!(hits.len(), 1);
+        assert_near(hits[0].point, (5.0, 5.0), 1e-4);
+
+        let hits = intersections("M-5 0 A5 5 0 0 1 5 0", "M-10 -5 H10");
+        assert_eq!(hits.len(), 1);
+        assert_near(hits[0].point, (0.0, -5.0), 1e-4);
+    }
+
+    #[test]
+    fn overlapping_lines_report_the_ends_of_the_overlap() {
+        let hits = intersections("M0 0 H10", "M5 0 H15");
+        let ends: Vec<_> = hits
+            .iter()
+            .map(|hit| (hit.point, hit.a.t, hit.b.t))
+            .collect();
+        assert_eq!(ends, vec![((5.0, 0.0), 0.5, 0.0), ((10.0, 0.0), 1.0, 0.5)]);
+    }
+
+    #[test]
+    fn looping_cubic() {
+        let hits = Path::init("M0 0 C20 10 -10 10 10 0").self_intersections();
+        assert_eq!(hits.len(), 1);
+        assert_near(hits[0].point, (5.0, 3.0), 1e-9);
+        // t = 1/2 ∓ √(3/20) solve x(t) = x(u), y(t) = y(u)
+        assert!((hits[0].a.t - (0.5 - 0.15f64.sqrt())).abs() < 1e-9);
+        assert!((hits[0].b.t - (0.5 + 0.15f64.sqrt())).abs() < 1e-9);
+    }
+
+    #[test]
+    fn figure_eight_reports_the_crossing_but_no_joints() {
+        let hits = Path::init("M0 0 L10 10 L10 0 L0 10 Z").self_intersections();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].point, (5.0, 5.0));
+        assert_eq!((hits[0].a.segment, hits[0].b.segment), (0, 2));
+    }
+
+    #[test]
+    fn single_segment_subpath_does_not_cross_its_own_closing_joint() {
+        assert!(Path::init("M0 0 C20 20 -20 20 0 0 Z")
+            .self_intersections()
+            .is_empty());
+        // the same segment in a longer subpath has a real crossing at its ends
+        let hits = Path::init("M0 0 C20 20 -20 20 0 0 L0 -10").self_intersections();
+        let crossing = hits
+            .iter()
+            .find(|hit| (hit.a.segment, hit.b.segment) == (0, 0))
+            .expect("the curve crosses itself");
+        assert_near(crossing.point, (0.0, 0.0), 1e-9);
+        assert_eq!((crossing.a.t, crossing.b.t), (0.0, 1.0));
+    }
+}
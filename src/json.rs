@@ -0,0 +1,148 @@
+use crate::{Path, Sample, Segment};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+/// A subpath with the numbers a viewer needs up front. Segments are in path coordinates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubpathSummary {
+    /// index into `Path::subpaths`, the same as `Sample::subpath`
+    pub index: usize,
+    pub closed: bool,
+    pub length: f64,
+    /// tight bounding box as (min, max), absent for a subpath without points
+    pub bounding_box: Option<((f64, f64), (f64, f64))>,
+    pub segments: Vec<Segment>,
+}
+
+/// Everything `write_json` exports, as one document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PathDocument {
+    pub subpaths: Vec<SubpathSummary>,
+    pub samples: Vec<Sample>,
+}
+
+/// One line of `write_ndjson`, tagged with its kind in a `record` field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "record", rename_all = "lowercase")]
+pub enum JsonRecord {
+    Subpath(SubpathSummary),
+    Sample(Sample),
+}
+
+impl Path {
+    /// Segments, metadata and samples of every subpath
+    pub fn document(&self) -> PathDocument {
+        let subpaths = self
+            .subpaths()
+            .into_iter()
+            .enumerate()
+            .map(|(index, subpath)| SubpathSummary {
+                index,
+                closed: subpath.closed,
+                length: subpath.segments.iter().map(Segment::length).sum(),
+                bounding_box: subpath.bounding_box(),
+                segments: subpath.segments,
+            })
+            .collect();
+
+        PathDocument {
+            subpaths,
+            samples: self.samples(),
+        }
+    }
+
+    /// Writes `document` as a single JSON object
+    pub fn write_json<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        serde_json::to_writer(&mut *writer, &self.document())?;
+        writeln!(writer)
+    }
+
+    pub fn save_json(&self, filename: &str) -> io::Result<()> {
+        let mut file = BufWriter::new(File::create(filename)?);
+        self.write_json(&mut file)?;
+        file.flush()
+    }
+
+    /// Writes `document` as newline-delimited JSON for streaming: a record for every
+    /// subpath first, then one for every sample
+    pub fn write_ndjson<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let document = self.document();
+        let records = document
+            .subpaths
+            .into_iter()
+            .map(JsonRecord::Subpath)
+            .chain(document.samples.into_iter().map(JsonRecord::Sample));
+
+        for record in records {
+            serde_json::to_writer(&mut *writer, &record)?;
+            writeln!(writer)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn save_ndjson(&self, filename: &str) -> io::Result<()> {
+        let mut file = BufWriter::new(File::create(filename)?);
+        self.write_ndjson(&mut file)?;
+        file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    const DATA: &str = "M0 0 H10 M0 5 L3 9";
+
+    #[test]
+    fn document_shape() {
+        let path = Path::init(DATA);
+        let mut out = vec![];
+        path.write_json(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+
+        // fields are written in declaration order, segments tagged with their kind
+        let subpaths = concat!(
+            r#"{"subpaths":[{"index":0,"closed":false,"length":10.0,"#,
+            r#""bounding_box":[[0.0,0.0],[10.0,0.0]],"#,
+            r#""segments":[{"type":"line","start":[0.0,0.0],"end":[10.0,0.0]}]},"#,
+            r#"{"index":1,"closed":false,"length":5.0,"#,
+            r#""bounding_box":[[0.0,5.0],[3.0,9.0]],"#,
+            r#""segments":[{"type":"line","start":[0.0,5.0],"end":[3.0,9.0]}]}],"#,
+            r#""samples":[{"point":["#,
+        );
+        assert!(text.starts_with(subpaths), "{}", &text[..400]);
+
+        let document: Value = serde_json::from_str(&text).unwrap();
+        let samples = document["samples"].as_array().unwrap();
+        assert_eq!(samples.len(), path.samples().len());
+        assert_eq!(samples[0].as_object().unwrap().len(), 7);
+    }
+
+    #[test]
+    fn ndjson_streams_subpaths_then_samples() {
+        let path = Path::init(DATA);
+        let mut out = vec![];
+        path.write_ndjson(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+
+        let records: Vec<JsonRecord> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let document = path.document();
+
+        assert_eq!(records.len(), 2 + document.samples.len());
+        assert!(text.starts_with(r#"{"record":"subpath","index":0,"#));
+        assert_eq!(
+            records[1],
+            JsonRecord::Subpath(document.subpaths[1].clone())
+        );
+        assert_eq!(records[2], JsonRecord::Sample(document.samples[0]));
+        assert!(records[2..]
+            .iter()
+            .all(|record| matches!(record, JsonRecord::Sample(_))));
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{Path, Sample, SampleColumn};
+use ndarray::{Array2, ShapeBuilder};
+
+/// Memory layout of the matrices built from samples
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MatrixOrder {
+    /// C order, each sample is contiguous
+    RowMajor,
+    /// Fortran order, each column is contiguous
+    ColumnMajor,
+}
+
+/// Builds an (N, k) matrix with one row per sample and one column per entry of `columns`
+pub fn samples_to_array(
+    samples: &[Sample],
+    columns: &[SampleColumn],
+    order: MatrixOrder,
+) -> Array2<f64> {
+    let shape = (samples.len(), columns.len());
+
+    match order {
+        MatrixOrder::RowMajor => {
+            let values = samples
+                .iter()
+                .flat_map(|sample| columns.iter().map(move |column| column.value(sample)))
+                .collect();
+            Array2::from_shape_vec(shape, values)
+        }
+        MatrixOrder::ColumnMajor => {
+            let values = columns
+                .iter()
+                .flat_map(|column| samples.iter().map(move |sample| column.value(sample)))
+                .collect();
+            Array2::from_shape_vec(shape.f(), values)
+        }
+    }
+    .expect("one value per sample and column")
+}
+
+impl Path {
+    /// Sampled points as an (N, 2) matrix of x, y
+    pub fn to_array(&self) -> Array2<f64> {
+        self.to_array_with(&[SampleColumn::X, SampleColumn::Y], MatrixOrder::RowMajor)
+    }
+
+    /// Sampled points as an (N, k) matrix with the chosen columns, e.g. tangents and lengths
+    pub fn to_array_with(&self, columns: &[SampleColumn], order: MatrixOrder) -> Array2<f64> {
+        samples_to_array(&self.samples(), columns, order)
+    }
+}
@@ -0,0 +1,213 @@
+use crate::segment::GAUSS_POINTS;
+use crate::{Path, Segment, Subpath};
+use std::f64::consts::PI;
+
+/// Direction in which a closed outline runs, as it appears when drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+}
+
+/// Area properties of a filled outline, in path coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AreaMoments {
+    /// signed area, positive for outlines that run clockwise on screen (y down)
+    pub area: f64,
+    pub centroid: (f64, f64),
+    /// second moment about the horizontal axis through the centroid, ∬ (y - cy)² dA
+    pub ixx: f64,
+    /// second moment about the vertical axis through the centroid, ∬ (x - cx)² dA
+    pub iyy: f64,
+    /// product of inertia about the centroid, ∬ (x - cx)(y - cy) dA
+    pub ixy: f64,
+}
+
+impl AreaMoments {
+    /// Area inertia tensor about the centroid, [[ixx, -ixy], [-ixy, iyy]]
+    pub fn inertia_tensor(&self) -> [[f64; 2]; 2] {
+        [[self.ixx, -self.ixy], [-self.ixy, self.iyy]]
+    }
+
+    /// Polar moment about the centroid, ixx + iyy
+    pub fn polar_moment(&self) -> f64 {
+        self.ixx + self.iyy
+    }
+
+    pub fn orientation(&self) -> Option<Orientation> {
+        if self.area > 0.0 {
+            Some(Orientation::Clockwise)
+        } else if self.area < 0.0 {
+            Some(Orientation::CounterClockwise)
+        } else {
+            None
+        }
+    }
+}
+
+/// Boundary integrals from Green's theorem, relative to a reference point:
+/// [∬ 1, ∬ x, ∬ y, ∬ x², ∬ y², ∬ xy]
+type Integrals = [f64; 6];
+
+/// Adds the contribution of `segment` to the boundary integrals. The integrands have degree
+/// at most 11 for cubics, so 7-point Gaussian quadrature is exact for Bézier segments.
+/// Arcs are integrated in pieces of at most 15 degrees.
+fn accumulate(segment: &Segment, origin: (f64, f64), integrals: &mut Integrals) {
+    let pieces = match segment.arc_center() {
+        Some(arc) => (arc.sweep_angle.abs() / (PI / 12.0)).ceil().max(1.0) as usize,
+        None => 1,
+    };
+
+    for piece in 0..pieces {
+        let t0 = piece as f64 / pieces as f64;
+        let half = 0.5 / pieces as f64;
+
+        for (x, w) in GAUSS_POINTS {
+            let t = t0 + (x + 1.0) * half;
+            let point = segment.point_at(t);
+            let (x, y) = (point.0 - origin.0, point.1 - origin.1);
+            let (dx, dy) = segment.derivative(t);
+            let weight = w * half;
+
+            integrals[0] += weight * (x * dy - y * dx) / 2.0;
+            integrals[1] += weight * x * x * dy / 2.0;
+            integrals[2] -= weight * y * y * dx / 2.0;
+            integrals[3] += weight * x * x * x * dy / 3.0;
+            integrals[4] -= weight * y * y * y * dx / 3.0;
+            integrals[5] += weight * x * x * y * dy / 2.0;
+        }
+    }
+}
+
+/// Area moments of the region bounded by `subpaths`, each closed with a line if open
+fn area_moments<'a>(subpaths: impl IntoIterator<Item = &'a Subpath>) -> AreaMoments {
+    let mut integrals: Integrals = [0.0; 6];
+    let mut origin = None;
+
+    for subpath in subpaths {
+        // integrate relative to a nearby point to keep the cubic terms well conditioned
+        let origin = *origin.get_or_insert(subpath.start);
+
+        for segment in &subpath.segments {
+            accumulate(segment, origin, &mut integrals);
+        }
+
+        let end = subpath.segments.last().map_or(subpath.start, Segment::end);
+        if end != subpath.start {
+            let closing = Segment::Line {
+                start: end,
+                end: subpath.start,
+            };
+            accumulate(&closing, origin, &mut integrals);
+        }
+    }
+
+    let origin = origin.unwrap_or((0.0, 0.0));
+    let [area, sx, sy, sxx, syy, sxy] = integrals;
+
+    if area == 0.0 {
+        return AreaMoments {
+            area,
+            centroid: origin,
+            ixx: 0.0,
+            iyy: 0.0,
+            ixy: 0.0,
+        };
+    }
+
+    let (cx, cy) = (sx / area, sy / area);
+
+    // parallel axis theorem; the sign of the area cancels so the moments are positive
+    let sign = area.signum();
+
+    AreaMoments {
+        area,
+        centroid: (origin.0 + cx, origin.1 + cy),
+        ixx: sign * (syy - area * cy * cy),
+        iyy: sign * (sxx - area * cx * cx),
+        ixy: sign * (sxy - area * cx * cy),
+    }
+}
+
+impl Subpath {
+    /// Area, centroid and second moments of the subpath, closed with a line if open
+    pub fn moments(&self) -> AreaMoments {
+        area_moments([self])
+    }
+
+    /// Signed area, positive for clockwise outlines as drawn (SVG's y axis points down)
+    pub fn signed_area(&self) -> f64 {
+        self.moments().area
+    }
+
+    /// `None` for outlines without area
+    pub fn orientation(&self) -> Option<Orientation> {
+        self.moments().orientation()
+    }
+}
+
+impl Path {
+    /// Area moments of all subpaths together. Subpaths with opposite orientation subtract,
+    /// so holes drawn the other way round are accounted for as in the nonzero fill.
+    pub fn moments(&self) -> AreaMoments {
+        area_moments(&self.subpaths())
+    }
+
+    pub fn signed_area(&self) -> f64 {
+        self.moments().area
+    }
+
+    pub fn centroid(&self) -> (f64, f64) {
+        self.moments().centroid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9 * expected.abs().max(1.0),
+            "{} instead of {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn square() {
+        let moments = Path::init("M0 0 H10 V10 H0 Z").moments();
+
+        assert_close(moments.area, 100.0);
+        assert_close(moments.centroid.0, 5.0);
+        assert_close(moments.centroid.1, 5.0);
+        // b h³ / 12
+        assert_close(moments.ixx, 10_000.0 / 12.0);
+        assert_close(moments.iyy, 10_000.0 / 12.0);
+        assert_close(moments.ixy, 0.0);
+        assert_eq!(moments.orientation(), Some(Orientation::Clockwise));
+
+        // drawn the other way round only the sign of the area changes
+        let reversed = Path::init("M0 0 V10 H10 V0 Z").moments();
+        assert_close(reversed.area, -100.0);
+        assert_close(reversed.ixx, 10_000.0 / 12.0);
+        assert_eq!(reversed.orientation(), Some(Orientation::CounterClockwise));
+    }
+
+    #[test]
+    fn circle() {
+        let moments = Path::init("M0 5 A5 5 0 0 1 10 5 A5 5 0 0 1 0 5 Z").moments();
+
+        assert_close(moments.area, 25.0 * PI);
+        assert_close(moments.centroid.0, 5.0);
+        assert_close(moments.centroid.1, 5.0);
+        // π r⁴ / 4 about any axis through the center
+        assert_close(moments.ixx, PI * 625.0 / 4.0);
+        assert_close(moments.iyy, PI * 625.0 / 4.0);
+        assert_close(moments.ixy, 0.0);
+        assert_close(moments.polar_moment(), PI * 625.0 / 2.0);
+    }
+}
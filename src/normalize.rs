@@ -0,0 +1,102 @@
+use crate::segment::lerp;
+use crate::{Path, Segment, Subpath};
+use std::f64::consts::PI;
+
+impl Segment {
+    /// Exact cubic form of the segment. Lines and quadratics are degree-elevated, arcs are
+    /// approximated with one cubic per quarter turn or less.
+    pub fn to_cubics(&self) -> Vec<Segment> {
+        match *self {
+            Segment::Line { start, end } => vec![Segment::Cubic {
+                start,
+                control1: lerp(start, end, 1.0 / 3.0),
+                control2: lerp(start, end, 2.0 / 3.0),
+                end,
+            }],
+            Segment::Quadratic {
+                start,
+                control,
+                end,
+            } => vec![Segment::Cubic {
+                start,
+                control1: lerp(start, control, 2.0 / 3.0),
+                control2: lerp(end, control, 2.0 / 3.0),
+                end,
+            }],
+            Segment::Cubic { .. } => vec![*self],
+            Segment::Arc { start, end, .. } => {
+                let Some(arc) = self.arc_center() else {
+                    return Segment::Line { start, end }.to_cubics();
+                };
+
+                let pieces = (arc.sweep_angle.abs() / (PI / 2.0) - 1e-9).ceil().max(1.0) as usize;
+                let step = arc.sweep_angle / pieces as f64;
+
+                // handle length for a circular arc of `step` radians on the unit circle
+                let k = 4.0 / 3.0 * (step / 4.0).tan();
+
+                let (sin_phi, cos_phi) = arc.rotation.sin_cos();
+                let map = |(x, y): (f64, f64)| {
+                    let (x, y) = (x * arc.radii.0, y * arc.radii.1);
+                    (
+                        arc.center.0 + cos_phi * x - sin_phi * y,
+                        arc.center.1 + sin_phi * x + cos_phi * y,
+                    )
+                };
+
+                (0..pieces)
+                    .map(|i| {
+                        let a0 = arc.start_angle + step * i as f64;
+                        let a1 = a0 + step;
+                        let (sin0, cos0) = a0.sin_cos();
+                        let (sin1, cos1) = a1.sin_cos();
+
+                        Segment::Cubic {
+                            start: if i == 0 { start } else { map((cos0, sin0)) },
+                            control1: map((cos0 - k * sin0, sin0 + k * cos0)),
+                            control2: map((cos1 + k * sin1, sin1 - k * cos1)),
+                            end: if i == pieces - 1 {
+                                end
+                            } else {
+                                map((cos1, sin1))
+                            },
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+impl Path {
+    /// Canonical form of the path: every segment becomes an absolute cubic, see
+    /// `Segment::to_cubics`
+    pub fn normalize(&self) -> Path {
+        self.normalized(false)
+    }
+
+    /// Same as `normalize`, but straight lines stay lines
+    pub fn normalize_keep_lines(&self) -> Path {
+        self.normalized(true)
+    }
+
+    fn normalized(&self, keep_lines: bool) -> Path {
+        let subpaths: Vec<Subpath> = self
+            .subpaths()
+            .into_iter()
+            .map(|subpath| Subpath {
+                segments: subpath
+                    .segments
+                    .iter()
+                    .flat_map(|segment| match segment {
+                        Segment::Line { .. } if keep_lines => vec![*segment],
+                        _ => segment.to_cubics(),
+                    })
+                    .collect(),
+                ..subpath
+            })
+            .collect();
+
+        Path::from_subpaths(&subpaths)
+    }
+}
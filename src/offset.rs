@@ -0,0 +1,620 @@
+use crate::fit::{least_squares_handles, unit};
+use crate::intersect::{self_intersect_subpaths, PathLocation};
+use crate::segment::distance;
+use crate::{Path, Segment, Subpath};
+
+/// Intervals scanned for cusps of the offset curve, where 1 + d·κ changes sign
+const CUSP_SCAN: usize = 64;
+/// Halvings of a piece before a fitted cubic is accepted whatever its error
+const MAX_FIT_DEPTH: usize = 10;
+/// Loops cut away from one offset subpath before giving up
+const MAX_LOOP_REMOVALS: usize = 100;
+
+/// Shape of the outside of a corner, as in SVG's `stroke-linejoin`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LineJoin {
+    Miter,
+    Round,
+    Bevel,
+}
+
+/// Settings for `Path::offset_with`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OffsetOptions {
+    pub join: LineJoin,
+    /// longest miter allowed, in multiples of twice the offset distance as for
+    /// `stroke-miterlimit`; longer miters are beveled
+    pub miter_limit: f64,
+    /// largest distance allowed between the fitted cubics and the true offset curve
+    pub tolerance: f64,
+}
+
+impl Default for OffsetOptions {
+    fn default() -> Self {
+        OffsetOptions {
+            join: LineJoin::Miter,
+            miter_limit: 4.0,
+            tolerance: 0.01,
+        }
+    }
+}
+
+/// Point at `t` moved by `d` to the left of the direction of travel as drawn (y down)
+fn offset_point(segment: &Segment, t: f64, d: f64) -> (f64, f64) {
+    let point = segment.point_at(t);
+    let (tx, ty) = segment.tangent(t);
+    (point.0 + d * ty, point.1 - d * tx)
+}
+
+/// Parameters where the offset curve has a cusp. The offset moves at (1 + d·κ) times the
+/// speed of the curve, so it turns back where the radius of curvature equals the distance.
+fn cusps(segment: &Segment, d: f64) -> Vec<f64> {
+    let speed = |t: f64| 1.0 + d * segment.curvature(t);
+    let mut cusps = vec![];
+    let mut previous = (0.0, speed(0.0));
+
+    for i in 1..=CUSP_SCAN {
+        let t = i as f64 / CUSP_SCAN as f64;
+        let value = speed(t);
+
+        if (previous.1 < 0.0) != (value < 0.0) {
+            let (mut low, mut high) = (previous.0, t);
+            for _ in 0..50 {
+                let middle = (low + high) / 2.0;
+                if (speed(middle) < 0.0) == (previous.1 < 0.0) {
+                    low = middle;
+                } else {
+                    high = middle;
+                }
+            }
+            cusps.push((low + high) / 2.0);
+        }
+        previous = (t, value);
+    }
+
+    cusps
+}
+
+/// Fits one cubic to the offset of `segment` between `t0` and `t1`, with the end points and
+/// tangent directions of the true offset and handle lengths from least squares (as in
+/// Schneider's curve fitting). Halves the range while the fit is off by more than `tolerance`.
+fn fit_offset(
+    segment: &Segment,
+    d: f64,
+    (t0, t1): (f64, f64),
+    tolerance: f64,
+    depth: usize,
+    out: &mut Vec<Segment>,
+) {
+    let target = |u: f64| offset_point(segment, t0 + (t1 - t0) * u, d);
+    let (p0, p3) = (target(0.0), target(1.0));
+
+    // tangents of the offset itself, which point backwards past a cusp
+    let h = 1e-4;
+    let chord = unit((p3.0 - p0.0, p3.1 - p0.1), (1.0, 0.0));
+    let near_start = target(h);
+    let near_end = target(1.0 - h);
+    let d0 = unit((near_start.0 - p0.0, near_start.1 - p0.1), chord);
+    let d3 = unit((p3.0 - near_end.0, p3.1 - near_end.1), chord);
+
+    let samples = (1..16).map(|i| {
+        let u = i as f64 / 16.0;
+        (u, target(u))
+    });
+
+    let (alpha, beta) = least_squares_handles(samples, (p0, d0), (p3, d3));
+
+    let cubic = Segment::Cubic {
+        start: p0,
+        control1: (p0.0 + alpha * d0.0, p0.1 + alpha * d0.1),
+        control2: (p3.0 - beta * d3.0, p3.1 - beta * d3.1),
+        end: p3,
+    };
+
+    let error = (0..16)
+        .map(|i| cubic.closest_point(target((i as f64 + 0.5) / 16.0)).2)
+        .fold(0.0, f64::max);
+
+    if error > tolerance && depth < MAX_FIT_DEPTH {
+        let middle = (t0 + t1) / 2.0;
+        fit_offset(segment, d, (t0, middle), tolerance, depth + 1, out);
+        fit_offset(segment, d, (middle, t1), tolerance, depth + 1, out);
+    } else {
+        out.push(cubic);
+    }
+}
+
+/// Offset of a single segment by `d`. Lines stay lines and circular arcs stay arcs, everything
+/// else becomes cubics split at the cusps of the offset.
+fn offset_segment(segment: &Segment, d: f64, tolerance: f64) -> Vec<Segment> {
+    if let Segment::Line { .. } = segment {
+        return vec![Segment::Line {
+            start: offset_point(segment, 0.0, d),
+            end: offset_point(segment, 1.0, d),
+        }];
+    }
+
+    if let (
+        Segment::Arc {
+            rotation,
+            large_arc,
+            sweep,
+            ..
+        },
+        Some(arc),
+    ) = (*segment, segment.arc_center())
+    {
+        let (rx, ry) = arc.radii;
+        let scale = 1.0 + d * segment.curvature(0.5);
+
+        // concentric circles: the radius simply grows or shrinks
+        if (rx - ry).abs() <= 1e-9 * rx && scale > 0.0 {
+            return vec![Segment::Arc {
+                start: offset_point(segment, 0.0, d),
+                radii: (rx * scale, rx * scale),
+                rotation,
+                large_arc,
+                sweep,
+                end: offset_point(segment, 1.0, d),
+            }];
+        }
+    }
+
+    let mut ts = vec![0.0];
+    ts.extend(cusps(segment, d));
+    ts.push(1.0);
+
+    let mut out = vec![];
+    for pair in ts.windows(2) {
+        fit_offset(segment, d, (pair[0], pair[1]), tolerance, 0, &mut out);
+    }
+    out
+}
+
+/// Segments leading from `from`, the end of the offset before `corner`, to `to`, the start
+/// of the offset after it. `incoming` and `outgoing` are the unit tangents of the path at the
+/// corner. Only the outside of a corner gets a join; on the inside the offsets overlap and
+/// are connected by a line, leaving a loop that is trimmed later.
+pub(crate) fn join(
+    corner: (f64, f64),
+    (from, to): ((f64, f64), (f64, f64)),
+    (incoming, outgoing): ((f64, f64), (f64, f64)),
+    d: f64,
+    options: &OffsetOptions,
+) -> Vec<Segment> {
+    let line = |start, end| Segment::Line { start, end };
+
+    if distance(from, to) <= 1e-9 * d.abs().max(1.0) {
+        return vec![];
+    }
+
+    let cross = incoming.0 * outgoing.1 - incoming.1 * outgoing.0;
+    let dot = incoming.0 * outgoing.0 + incoming.1 * outgoing.1;
+
+    // a path that doubles back turns towards whichever side is offset
+    let turn = if cross.abs() < 1e-9 {
+        if dot > 0.0 {
+            return vec![line(from, to)];
+        }
+        d.signum()
+    } else {
+        cross.signum()
+    };
+
+    // turning right as drawn opens a gap on the left, where positive offsets go
+    if turn * d < 0.0 {
+        return vec![line(from, to)];
+    }
+
+    match options.join {
+        LineJoin::Bevel => vec![line(from, to)],
+        LineJoin::Round => vec![Segment::Arc {
+            start: from,
+            radii: (d.abs(), d.abs()),
+            rotation: 0.0,
+            large_arc: false,
+            sweep: turn > 0.0,
+            end: to,
+        }],
+        LineJoin::Miter => {
+            // miter length over stroke width is 1 / sin(θ / 2), θ the angle between segments
+            let ratio = (2.0 / (1.0 + dot)).sqrt();
+
+            if 1.0 + dot < 1e-12 || ratio > options.miter_limit {
+                return vec![line(from, to)];
+            }
+
+            let normals = (incoming.1 + outgoing.1, -incoming.0 - outgoing.0);
+            let tip = (
+                corner.0 + d * normals.0 / (1.0 + dot),
+                corner.1 + d * normals.1 / (1.0 + dot),
+            );
+            vec![line(from, tip), line(tip, to)]
+        }
+    }
+}
+
+/// Segments of `segments` from `from` to `to`, wrapping around the end for closed subpaths
+fn portion(segments: &[Segment], from: PathLocation, to: PathLocation) -> Vec<Segment> {
+    if from.segment == to.segment && from.t <= to.t {
+        return vec![segments[from.segment].subsegment(from.t, to.t)];
+    }
+
+    let mut out = vec![segments[from.segment].subsegment(from.t, 1.0)];
+    let mut index = (from.segment + 1) % segments.len();
+    while index != to.segment {
+        out.push(segments[index]);
+        index = (index + 1) % segments.len();
+    }
+    out.push(segments[to.segment].subsegment(0.0, to.t));
+
+    out
+}
+
+/// Whether most of `segments`, sampled evenly by length, lies closer to `original` than the
+/// offset distance. Such parts are loops where the offset of one part of the path runs
+/// through the offset region of another, e.g. swallowtails at cusps and inside corners.
+fn is_spurious(segments: &[Segment], original: &[Segment], d: f64, tolerance: f64) -> bool {
+    let lengths: Vec<f64> = segments.iter().map(Segment::length).collect();
+    let total: f64 = lengths.iter().sum();
+    if total == 0.0 {
+        return false;
+    }
+
+    let slack = tolerance.max(d.abs() * 1e-3);
+    let samples = 9;
+    let mut close = 0;
+
+    for i in 0..samples {
+        let mut position = total * (i as f64 + 0.5) / samples as f64;
+        let mut point = segments[segments.len() - 1].end();
+
+        for (segment, length) in segments.iter().zip(&lengths) {
+            if position <= *length {
+                point = segment.point_at(segment.parameter_at_length(position));
+                break;
+            }
+            position -= length;
+        }
+
+        let nearest = original
+            .iter()
+            .map(|segment| segment.closest_point(point).2)
+            .fold(f64::INFINITY, f64::min);
+
+        if nearest < d.abs() - slack {
+            close += 1;
+        }
+    }
+
+    close * 2 > samples
+}
+
+/// Cuts the spurious loops out of a raw offset subpath, one intersection at a time
+fn remove_loops(mut subpath: Subpath, original: &Subpath, d: f64, tolerance: f64) -> Subpath {
+    for _ in 0..MAX_LOOP_REMOVALS {
+        let segments = subpath.segments.clone();
+        if segments.is_empty() {
+            break;
+        }
+
+        let last = PathLocation {
+            subpath: 0,
+            segment: segments.len() - 1,
+            t: 1.0,
+        };
+        let first = PathLocation {
+            subpath: 0,
+            segment: 0,
+            t: 0.0,
+        };
+
+        let mut trimmed = None;
+
+        for intersection in self_intersect_subpaths(std::slice::from_ref(&subpath)) {
+            let (a, b) = (intersection.a, intersection.b);
+
+            // cubics fitted on either side of a cusp touch there and may cross within the
+            // tolerance, which is no loop of the offset
+            let joint = if b.segment == a.segment + 1 {
+                Some(segments[a.segment].end())
+            } else if subpath.closed && a.segment == 0 && b.segment == segments.len() - 1 {
+                Some(segments[0].start())
+            } else {
+                None
+            };
+            if joint.is_some_and(|joint| distance(joint, intersection.point) <= tolerance) {
+                continue;
+            }
+
+            let inner = portion(&segments, a, b);
+
+            if is_spurious(&inner, &original.segments, d, tolerance) {
+                trimmed = Some(if subpath.closed {
+                    portion(&segments, b, a)
+                } else {
+                    let mut kept = portion(&segments, first, a);
+                    kept.extend(portion(&segments, b, last));
+                    kept
+                });
+                break;
+            }
+
+            if subpath.closed
+                && is_spurious(&portion(&segments, b, a), &original.segments, d, tolerance)
+            {
+                trimmed = Some(inner);
+                break;
+            }
+        }
+
+        let Some(mut kept) = trimmed else {
+            break;
+        };
+
+        kept.retain(|segment| segment.length() > 0.0);
+        if let Some(first) = kept.first() {
+            subpath.start = first.start();
+        }
+
+        // both ends were cut at the same crossing, make them meet exactly again
+        if subpath.closed {
+            if let Some(last) = kept.last_mut() {
+                *last = last.with_endpoints(last.start(), subpath.start);
+            }
+        }
+        subpath.segments = kept;
+    }
+
+    subpath
+}
+
+/// Whether line `b` continues line `a` in the same direction
+fn continues(a: &Segment, b: &Segment) -> bool {
+    let (Segment::Line { start, end }, Segment::Line { end: next, .. }) = (*a, *b) else {
+        return false;
+    };
+
+    let (u, v) = (
+        (end.0 - start.0, end.1 - start.1),
+        (next.0 - end.0, next.1 - end.1),
+    );
+    let cross = u.0 * v.1 - u.1 * v.0;
+    let dot = u.0 * v.0 + u.1 * v.1;
+    dot > 0.0 && cross.abs() <= 1e-9 * u.0.hypot(u.1) * v.0.hypot(v.1)
+}
+
+/// Joins lines that continue each other into one, such as a side and the first half of the
+/// miter after it, including the last and first line of a closed subpath
+fn merge_collinear(mut subpath: Subpath) -> Subpath {
+    let mut merged: Vec<Segment> = vec![];
+
+    for segment in subpath.segments {
+        match merged.last_mut() {
+            Some(last) if continues(last, &segment) => {
+                *last = Segment::Line {
+                    start: last.start(),
+                    end: segment.end(),
+                };
+            }
+            _ => merged.push(segment),
+        }
+    }
+
+    if subpath.closed && merged.len() > 2 && continues(&merged[merged.len() - 1], &merged[0]) {
+        let last = merged.pop().expect("more than two segments");
+        merged[0] = Segment::Line {
+            start: last.start(),
+            end: merged[0].end(),
+        };
+        subpath.start = last.start();
+    }
+
+    subpath.segments = merged;
+    subpath
+}
+
+/// Offset of one subpath with joins at its corners and loops removed, `None` where a closed
+/// outline vanishes
+pub(crate) fn offset_subpath(
+    subpath: &Subpath,
+    d: f64,
+    options: &OffsetOptions,
+) -> Option<Subpath> {
+    let segments: Vec<&Segment> = subpath
+        .segments
+        .iter()
+        .filter(|segment| segment.length() > 0.0)
+        .collect();
+
+    let pieces: Vec<Vec<Segment>> = segments
+        .iter()
+        .map(|segment| offset_segment(segment, d, options.tolerance))
+        .collect();
+
+    let mut out = vec![];
+
+    for (i, (segment, piece)) in segments.iter().zip(&pieces).enumerate() {
+        if i > 0 {
+            let previous = segments[i - 1];
+            out.extend(join(
+                segment.start(),
+                (
+                    offset_point(previous, 1.0, d),
+                    offset_point(segment, 0.0, d),
+                ),
+                (previous.tangent(1.0), segment.tangent(0.0)),
+                d,
+                options,
+            ));
+        }
+        out.extend(piece);
+    }
+
+    if subpath.closed && !segments.is_empty() {
+        let (last, first) = (segments[segments.len() - 1], segments[0]);
+        out.extend(join(
+            first.start(),
+            (offset_point(last, 1.0, d), offset_point(first, 0.0, d)),
+            (last.tangent(1.0), first.tangent(0.0)),
+            d,
+            options,
+        ));
+    }
+
+    let raw = Subpath {
+        start: out.first().map_or(subpath.start, Segment::start),
+        segments: out,
+        closed: subpath.closed,
+    };
+
+    let trimmed = remove_loops(raw, subpath, d, options.tolerance);
+
+    // an outline offset inwards past its own width turns inside out and lies entirely
+    // too close to the original
+    if trimmed.closed && is_spurious(&trimmed.segments, &subpath.segments, d, options.tolerance) {
+        return None;
+    }
+
+    Some(merge_collinear(trimmed))
+}
+
+impl Path {
+    /// Parallel path at `distance`, with mitered corners and a tolerance of 0.01
+    pub fn offset(&self, distance: f64) -> Path {
+        self.offset_with(distance, &OffsetOptions::default())
+    }
+
+    /// Parallel path at `distance`. Positive distances go to the left of the direction of
+    /// travel as drawn (SVG's y axis points down), which is outwards for clockwise outlines;
+    /// negative ones go to the right. Curves are replaced by cubics fitted to the true offset,
+    /// lines and circular arcs are offset exactly.
+    pub fn offset_with(&self, distance: f64, options: &OffsetOptions) -> Path {
+        let subpaths: Vec<Subpath> = self
+            .subpaths()
+            .iter()
+            .filter(|subpath| !subpath.segments.is_empty())
+            .filter_map(|subpath| offset_subpath(subpath, distance, options))
+            .collect();
+
+        Path::from_subpaths(&subpaths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: &str = "M0 0 H10 V10 H0 Z";
+
+    fn with_join(join: LineJoin) -> OffsetOptions {
+        OffsetOptions {
+            join,
+            ..OffsetOptions::default()
+        }
+    }
+
+    /// Smallest and largest distance from points along `path` to `original`
+    fn distance_range(path: &Path, original: &Path) -> (f64, f64) {
+        path.segments()
+            .iter()
+            .flat_map(|segment| (0..=32).map(move |i| segment.point_at(i as f64 / 32.0)))
+            .map(|point| original.distance_to(point).unwrap())
+            .fold((f64::INFINITY, 0.0), |(min, max), d| {
+                (min.min(d), max.max(d))
+            })
+    }
+
+    #[test]
+    fn joins_outside_corners() {
+        let square = Path::init(SQUARE);
+
+        let miter = square.offset_with(1.0, &with_join(LineJoin::Miter));
+        assert_eq!(miter.to_path_data(), "M -1 -1 L 11 -1 L 11 11 L -1 11 Z");
+
+        let round = square.offset_with(1.0, &with_join(LineJoin::Round));
+        assert!((round.signed_area() - (140.0 + std::f64::consts::PI)).abs() < 1e-9);
+        assert_eq!(round.segments().len(), 8);
+
+        let bevel = square.offset_with(1.0, &with_join(LineJoin::Bevel));
+        assert!((bevel.signed_area() - 142.0).abs() < 1e-9);
+        assert_eq!(bevel.segments().len(), 8);
+
+        // a right angle needs a miter limit of √2
+        let limited = square.offset_with(
+            1.0,
+            &OffsetOptions {
+                miter_limit: 1.4,
+                ..OffsetOptions::default()
+            },
+        );
+        assert_eq!(limited.to_path_data(), bevel.to_path_data());
+    }
+
+    #[test]
+    fn trims_inside_corners() {
+        assert_eq!(
+            Path::init("M0 0 H10 V10").offset(-1.0).to_path_data(),
+            "M 0 1 L 9 1 L 9 10"
+        );
+        assert_eq!(
+            Path::init(SQUARE).offset(-1.0).to_path_data(),
+            "M 1 1 L 9 1 L 9 9 L 1 9 Z"
+        );
+    }
+
+    #[test]
+    fn collapsing_outlines_vanish() {
+        assert!(Path::init(SQUARE).offset(-6.0).subpaths().is_empty());
+        assert!(Path::init("M0 0 A5 5 0 0 1 10 0 A5 5 0 0 1 0 0 Z")
+            .offset(-6.0)
+            .subpaths()
+            .is_empty());
+    }
+
+    #[test]
+    fn finds_cusps_where_the_offset_turns_back() {
+        let bend = Path::init("M0 0 C10 0 10 0 10 10");
+        let segment = bend.segments()[0];
+
+        // the radius of curvature goes down to about 2.65 in the middle of the bend
+        let ts = cusps(&segment, -3.0);
+        assert_eq!(ts.len(), 2);
+        for t in ts {
+            assert!((1.0 - 3.0 * segment.curvature(t)).abs() < 1e-6);
+        }
+        assert!(cusps(&segment, -2.0).is_empty());
+
+        // the swallowtail between the cusps is cut away, all that is left is 3 away
+        let offset = bend.offset(-3.0);
+        let (min, max) = distance_range(&offset, &bend);
+        assert!(min > 3.0 - 0.01 && max < 3.0 + 0.01, "{} {}", min, max);
+        assert!(self_intersect_subpaths(&offset.subpaths()).is_empty());
+    }
+
+    #[test]
+    fn fitted_curves_stay_within_the_tolerance() {
+        let ellipse = Path::init("M0 0 A20 10 0 0 1 40 0");
+        let mut counts = vec![];
+
+        for tolerance in [0.01, 1e-4] {
+            let options = OffsetOptions {
+                tolerance,
+                ..OffsetOptions::default()
+            };
+            let offset = ellipse.offset_with(3.0, &options);
+            let (min, max) = distance_range(&offset, &ellipse);
+            assert!(
+                min > 3.0 - tolerance && max < 3.0 + tolerance,
+                "{} {}",
+                min,
+                max
+            );
+            counts.push(offset.segments().len());
+        }
+
+        assert!(counts[1] > counts[0], "{:?}", counts);
+    }
+}
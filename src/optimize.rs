@@ -0,0 +1,781 @@
+use crate::segment::{distance, distance_to_line_segment, ArcCenter};
+use crate::{Path, Segment, Subpath};
+use std::f64::consts::PI;
+
+/// Previous control point, kept for S/T reflection
+#[derive(Debug, Clone, Copy)]
+enum Control {
+    Cubic((f64, f64)),
+    Quadratic((f64, f64)),
+}
+
+/// One possible encoding of a segment
+struct Candidate {
+    command: char,
+    numbers: Vec<String>,
+    end: (f64, f64),
+    control: Option<Control>,
+}
+
+/// Writes path data with the fewest characters: omits repeated command letters and every
+/// separator the grammar does not need
+struct Writer {
+    out: String,
+    last_command: Option<char>,
+    last_number: Option<String>,
+}
+
+impl Writer {
+    fn encode(&self, command: char, numbers: &[String]) -> String {
+        let mut text = String::new();
+        let mut previous = self.last_number.clone();
+
+        let implicit = match (self.last_command, command) {
+            (Some('M'), 'L') | (Some('m'), 'l') => true,
+            (Some(last), _) => last == command && !matches!(command, 'M' | 'm'),
+            (None, _) => false,
+        };
+
+        if !implicit || numbers.is_empty() {
+            text.push(command);
+            previous = None;
+        }
+
+        for number in numbers {
+            if let Some(previous) = previous {
+                let needs_separator = !(number.starts_with('-')
+                    || (number.starts_with('.')
+                        && previous.contains('.')
+                        && !previous.contains('e')));
+                if needs_separator {
+                    text.push(' ');
+                }
+            }
+            text.push_str(number);
+            previous = Some(number.clone());
+        }
+
+        text
+    }
+
+    fn push(&mut self, command: char, numbers: &[String]) {
+        let text = self.encode(command, numbers);
+        self.out.push_str(&text);
+
+        // after a moveto, further coordinate pairs are implicit linetos
+        self.last_command = Some(match command {
+            'M' => 'L',
+            'm' => 'l',
+            _ => command,
+        });
+        self.last_number = numbers.last().cloned();
+    }
+}
+
+struct Optimizer {
+    tolerance: f64,
+    decimals: Option<usize>,
+    current: (f64, f64),
+    control: Option<Control>,
+    writer: Writer,
+}
+
+/// Formats a number with the minimum number of characters, e.g. "-0.50" as "-.5", rounded
+/// to `decimals` places if given
+fn format_number(value: f64, decimals: Option<usize>) -> String {
+    let exact = value.to_string();
+    let mut text = match decimals {
+        // the shortest form of the value itself wins when rounding only adds digits
+        Some(decimals) => {
+            let rounded = format!("{:.*}", decimals, value);
+            if exact.len() < rounded.len() {
+                exact
+            } else {
+                rounded
+            }
+        }
+        None => exact,
+    };
+
+    if text.contains('.') && !text.contains('e') {
+        text = text.trim_end_matches('0').trim_end_matches('.').to_string();
+    }
+    if text == "-0" {
+        text = "0".to_string();
+    }
+    if let Some(rest) = text.strip_prefix("0.") {
+        text = format!(".{}", rest);
+    } else if let Some(rest) = text.strip_prefix("-0.") {
+        text = format!("-.{}", rest);
+    }
+
+    text
+}
+
+fn parse_number(text: &str) -> f64 {
+    text.parse().expect("not valid nr")
+}
+
+impl Optimizer {
+    /// Rounded coordinates of `point`, relative to the current point when `relative` is set,
+    /// together with the point they actually encode
+    fn coordinates(&self, point: (f64, f64), relative: bool) -> ([String; 2], (f64, f64)) {
+        self.coordinates_to(point, relative, self.decimals)
+    }
+
+    /// `coordinates` rounded to `decimals` places instead of the default
+    fn coordinates_to(
+        &self,
+        point: (f64, f64),
+        relative: bool,
+        decimals: Option<usize>,
+    ) -> ([String; 2], (f64, f64)) {
+        let origin = if relative { self.current } else { (0.0, 0.0) };
+        let x = format_number(point.0 - origin.0, decimals);
+        let y = format_number(point.1 - origin.1, decimals);
+        let encoded = (origin.0 + parse_number(&x), origin.1 + parse_number(&y));
+
+        ([x, y], encoded)
+    }
+
+    fn moveto(&mut self, point: (f64, f64)) {
+        let candidates: Vec<Candidate> = [('M', false), ('m', true)]
+            .into_iter()
+            .map(|(command, relative)| {
+                let (numbers, end) = self.coordinates(point, relative);
+                Candidate {
+                    command,
+                    numbers: numbers.to_vec(),
+                    end,
+                    control: None,
+                }
+            })
+            .collect();
+
+        self.commit(candidates);
+    }
+
+    fn segment(&mut self, segment: Segment) {
+        // half of the tolerance for moving points, the other half for simpler segments
+        self.segment_within(segment, self.tolerance / 2.0);
+    }
+
+    /// Writes a segment, replacing it with a simpler one if that stays within `simplification`
+    fn segment_within(&mut self, segment: Segment, simplification: f64) {
+        let budget = self.tolerance / 2.0;
+
+        // drop segments that never get further than the budget from the point written last,
+        // so that a run of short segments is not dropped as a whole
+        if reach(self.current, &segment) <= budget {
+            return;
+        }
+
+        let segment = simplify(segment, simplification);
+        let mut candidates = vec![];
+
+        for relative in [false, true] {
+            let case = |command: char| {
+                if relative {
+                    command.to_ascii_lowercase()
+                } else {
+                    command
+                }
+            };
+
+            match segment {
+                Segment::Line { end, .. } => {
+                    let (numbers, encoded) = self.coordinates(end, relative);
+                    candidates.push(Candidate {
+                        command: case('L'),
+                        numbers: numbers.to_vec(),
+                        end: encoded,
+                        control: None,
+                    });
+
+                    if distance((encoded.0, self.current.1), end) <= budget {
+                        let [x, _] = numbers.clone();
+                        candidates.push(Candidate {
+                            command: case('H'),
+                            numbers: vec![x],
+                            end: (encoded.0, self.current.1),
+                            control: None,
+                        });
+                    }
+                    if distance((self.current.0, encoded.1), end) <= budget {
+                        let [_, y] = numbers;
+                        candidates.push(Candidate {
+                            command: case('V'),
+                            numbers: vec![y],
+                            end: (self.current.0, encoded.1),
+                            control: None,
+                        });
+                    }
+                }
+                Segment::Quadratic { control, end, .. } => {
+                    let (control_numbers, encoded_control) = self.coordinates(control, relative);
+                    let (end_numbers, encoded_end) = self.coordinates(end, relative);
+
+                    candidates.push(Candidate {
+                        command: case('Q'),
+                        numbers: [control_numbers, end_numbers.clone()].concat(),
+                        end: encoded_end,
+                        control: Some(Control::Quadratic(encoded_control)),
+                    });
+
+                    if let Some(Control::Quadratic(previous)) = self.control {
+                        let reflected = reflect(previous, self.current);
+                        if distance(reflected, control) <= budget {
+                            candidates.push(Candidate {
+                                command: case('T'),
+                                numbers: end_numbers.to_vec(),
+                                end: encoded_end,
+                                control: Some(Control::Quadratic(reflected)),
+                            });
+                        }
+                    }
+                }
+                Segment::Cubic {
+                    control1,
+                    control2,
+                    end,
+                    ..
+                } => {
+                    let (control1_numbers, _) = self.coordinates(control1, relative);
+                    let (control2_numbers, encoded_control2) = self.coordinates(control2, relative);
+                    let (end_numbers, encoded_end) = self.coordinates(end, relative);
+
+                    candidates.push(Candidate {
+                        command: case('C'),
+                        numbers: [
+                            control1_numbers,
+                            control2_numbers.clone(),
+                            end_numbers.clone(),
+                        ]
+                        .concat(),
+                        end: encoded_end,
+                        control: Some(Control::Cubic(encoded_control2)),
+                    });
+
+                    if let Some(Control::Cubic(previous)) = self.control {
+                        if distance(reflect(previous, self.current), control1) <= budget {
+                            candidates.push(Candidate {
+                                command: case('S'),
+                                numbers: [control2_numbers, end_numbers].concat(),
+                                end: encoded_end,
+                                control: Some(Control::Cubic(encoded_control2)),
+                            });
+                        }
+                    }
+                }
+                Segment::Arc { .. } => {
+                    if let Some((numbers, encoded_end)) = self.arc(segment, relative) {
+                        candidates.push(Candidate {
+                            command: case('A'),
+                            numbers,
+                            end: encoded_end,
+                            control: None,
+                        });
+                    }
+                }
+            }
+        }
+
+        // an arc so eccentric that moving its start point to the point written last changes
+        // it by more than the tolerance is written as cubics, which move no further than
+        // their points do
+        if candidates.is_empty() {
+            let mut cubics = vec![];
+            arc_to_cubics(&segment, simplification / 2.0, &mut cubics);
+            for cubic in cubics {
+                self.segment_within(cubic, simplification / 2.0);
+            }
+            return;
+        }
+
+        self.commit(candidates);
+    }
+
+    /// Numbers of an arc and the end point they encode. Rounding the rotation by Δθ turns
+    /// the arc about its center, which moves it by at most r·Δθ, so the rotation keeps
+    /// enough decimals for that to stay within half the tolerance. The effect of rounded
+    /// radii depends on how the center moves with them, so radii and end point get more
+    /// decimals until the arc written is within the tolerance of the original, or `None`
+    /// if no number of decimals gets it there.
+    fn arc(&self, segment: Segment, relative: bool) -> Option<(Vec<String>, (f64, f64))> {
+        let Segment::Arc {
+            radii,
+            rotation,
+            large_arc,
+            sweep,
+            end,
+            ..
+        } = segment
+        else {
+            unreachable!("only called for arcs");
+        };
+
+        let numbers = |decimals: Option<usize>, rotation_decimals: Option<usize>| {
+            let (end_numbers, encoded_end) = self.coordinates_to(end, relative, decimals);
+            let mut numbers = vec![
+                format_number(radii.0.abs(), decimals),
+                format_number(radii.1.abs(), decimals),
+                format_number(rotation, rotation_decimals),
+                (large_arc as u8).to_string(),
+                (sweep as u8).to_string(),
+            ];
+            numbers.extend(end_numbers);
+            (numbers, encoded_end)
+        };
+
+        let Some(decimals) = self.decimals else {
+            return Some(numbers(None, None));
+        };
+
+        // radii as drawn, scaled up when they are too small to reach the end point
+        let radius = segment
+            .arc_center()
+            .map_or(0.0, |arc| arc.radii.0.max(arc.radii.1));
+        let budget = self.tolerance / 2.0;
+        let turn = |decimals: usize| radius * 0.5 * 10f64.powi(-(decimals as i32)) * PI / 180.0;
+        let mut rotation_decimals = decimals;
+        while rotation_decimals < 15 && turn(rotation_decimals) > budget {
+            rotation_decimals += 1;
+        }
+
+        for extra in 0..=15 - decimals {
+            let (numbers, encoded_end) = numbers(
+                Some(decimals + extra),
+                Some((rotation_decimals + extra).min(15)),
+            );
+            let written = Segment::Arc {
+                start: self.current,
+                radii: (parse_number(&numbers[0]), parse_number(&numbers[1])),
+                rotation: parse_number(&numbers[2]),
+                large_arc,
+                sweep,
+                end: encoded_end,
+            };
+
+            if deviation(&segment, &written, self.tolerance) <= self.tolerance {
+                return Some((numbers, encoded_end));
+            }
+        }
+
+        None
+    }
+
+    /// Writes the shortest candidate and moves the current point to what it encodes
+    fn commit(&mut self, candidates: Vec<Candidate>) {
+        let best = candidates
+            .into_iter()
+            .min_by_key(|candidate| {
+                self.writer
+                    .encode(candidate.command, &candidate.numbers)
+                    .len()
+            })
+            .expect("at least one candidate");
+
+        self.writer.push(best.command, &best.numbers);
+        self.current = best.end;
+        self.control = best.control;
+    }
+}
+
+/// Replaces a segment with a lower-degree one when that stays within `budget`
+fn simplify(segment: Segment, budget: f64) -> Segment {
+    match segment {
+        Segment::Quadratic {
+            start,
+            control,
+            end,
+        } => {
+            if distance_to_line_segment(control, start, end) <= budget {
+                Segment::Line { start, end }
+            } else {
+                segment
+            }
+        }
+        Segment::Cubic {
+            start,
+            control1,
+            control2,
+            end,
+        } => {
+            // degenerate cubics, such as the ones transform_line_to_cbezier creates
+            if distance_to_line_segment(control1, start, end) <= budget
+                && distance_to_line_segment(control2, start, end) <= budget
+            {
+                return Segment::Line { start, end };
+            }
+
+            // a cubic is a degree-elevated quadratic when both control points imply the same
+            // quadratic control point; sqrt(3) / 36 * |p3 - 3 p2 + 3 p1 - p0| bounds the error
+            let dx = end.0 - 3.0 * control2.0 + 3.0 * control1.0 - start.0;
+            let dy = end.1 - 3.0 * control2.1 + 3.0 * control1.1 - start.1;
+            if 3f64.sqrt() / 36.0 * dx.hypot(dy) <= budget {
+                Segment::Quadratic {
+                    start,
+                    control: (
+                        (3.0 * (control1.0 + control2.0) - start.0 - end.0) / 4.0,
+                        (3.0 * (control1.1 + control2.1) - start.1 - end.1) / 4.0,
+                    ),
+                    end,
+                }
+            } else {
+                segment
+            }
+        }
+        Segment::Arc {
+            start, radii, end, ..
+        } => {
+            if radii.0 == 0.0 || radii.1 == 0.0 {
+                Segment::Line { start, end }
+            } else {
+                segment
+            }
+        }
+        Segment::Line { .. } => segment,
+    }
+}
+
+/// Cubics within `budget` of an arc, halving it until the cubic of each piece is close enough
+fn arc_to_cubics(arc: &Segment, budget: f64, out: &mut Vec<Segment>) {
+    match arc.arc_center() {
+        Some(center) => arc_piece_to_cubics(
+            &center,
+            center.start_angle,
+            center.sweep_angle,
+            budget,
+            0,
+            out,
+        ),
+        None => out.push(Segment::Line {
+            start: arc.start(),
+            end: arc.end(),
+        }),
+    }
+}
+
+/// `arc_to_cubics` for the part of `arc` from `start_angle` turning by `sweep_angle`
+fn arc_piece_to_cubics(
+    arc: &ArcCenter,
+    start_angle: f64,
+    sweep_angle: f64,
+    budget: f64,
+    depth: usize,
+    out: &mut Vec<Segment>,
+) {
+    let end_angle = start_angle + sweep_angle;
+    let (start, end) = (
+        arc.point_at_angle(start_angle),
+        arc.point_at_angle(end_angle),
+    );
+
+    // derivative of the arc by its angle
+    let (sin_phi, cos_phi) = arc.rotation.sin_cos();
+    let tangent = |angle: f64| {
+        let x = -arc.radii.0 * angle.sin();
+        let y = arc.radii.1 * angle.cos();
+        (cos_phi * x - sin_phi * y, sin_phi * x + cos_phi * y)
+    };
+
+    // handles of 4/3 tan(θ/4) put the middle of the cubic on a circular arc
+    let k = 4.0 / 3.0 * (sweep_angle / 4.0).tan();
+    let (t0, t1) = (tangent(start_angle), tangent(end_angle));
+    let cubic = Segment::Cubic {
+        start,
+        control1: (start.0 + k * t0.0, start.1 + k * t0.1),
+        control2: (end.0 - k * t1.0, end.1 - k * t1.1),
+        end,
+    };
+    let piece = Segment::Arc {
+        start,
+        radii: arc.radii,
+        rotation: arc.rotation.to_degrees(),
+        large_arc: sweep_angle.abs() > PI,
+        sweep: sweep_angle > 0.0,
+        end,
+    };
+
+    if depth >= 16 || deviation(&piece, &cubic, budget) <= budget {
+        out.push(cubic);
+        return;
+    }
+
+    let half = sweep_angle / 2.0;
+    arc_piece_to_cubics(arc, start_angle, half, budget, depth + 1, out);
+    arc_piece_to_cubics(arc, start_angle + half, half, budget, depth + 1, out);
+}
+
+/// Largest distance between `point` and the segment, or a bound on it
+fn reach(point: (f64, f64), segment: &Segment) -> f64 {
+    let farthest = |points: &[(f64, f64)]| {
+        points
+            .iter()
+            .map(|p| distance(point, *p))
+            .fold(0.0, f64::max)
+    };
+
+    match *segment {
+        Segment::Line { start, end } => farthest(&[start, end]),
+        // a Bézier curve lies within the hull of its control points
+        Segment::Quadratic {
+            start,
+            control,
+            end,
+        } => farthest(&[start, control, end]),
+        Segment::Cubic {
+            start,
+            control1,
+            control2,
+            end,
+        } => farthest(&[start, control1, control2, end]),
+        // no point of an arc is further from its start than its length
+        Segment::Arc { start, .. } => distance(point, start) + segment.length(),
+    }
+}
+
+/// Distance from `point` to `segment`, from the point at parameter `t` unless that is
+/// further than `tolerance`, in which case the closest point near `t` is searched for. It
+/// is never less than the true distance, so it is safe to compare with the tolerance.
+fn distance_near(point: (f64, f64), segment: &Segment, t: f64, tolerance: f64) -> f64 {
+    let at = |s: f64| distance(point, segment.point_at(s));
+    let direct = at(t);
+    if direct <= tolerance {
+        return direct;
+    }
+
+    // golden-section search, the distance having a single minimum this close to `t`
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    let (mut low, mut high) = ((t - 1.0 / 16.0).max(0.0), (t + 1.0 / 16.0).min(1.0));
+    let (mut a, mut b) = (high - ratio * (high - low), low + ratio * (high - low));
+    let (mut at_a, mut at_b) = (at(a), at(b));
+    for _ in 0..40 {
+        if at_a < at_b {
+            (high, b, at_b) = (b, a, at_a);
+            a = high - ratio * (high - low);
+            at_a = at(a);
+        } else {
+            (low, a, at_a) = (a, b, at_b);
+            b = low + ratio * (high - low);
+            at_b = at(b);
+        }
+    }
+
+    direct.min(at_a).min(at_b)
+}
+
+/// Largest distance from a point on one segment to the other, both ways, measured at steps
+/// short enough to resolve a small fraction of `tolerance`
+fn deviation(a: &Segment, b: &Segment, tolerance: f64) -> f64 {
+    let length = a.length().max(b.length());
+    let steps = (length / (tolerance / 16.0)).ceil().clamp(64.0, 4096.0) as usize;
+
+    let one_way = |from: &Segment, to: &Segment| {
+        (0..=steps)
+            .map(|i| {
+                let t = i as f64 / steps as f64;
+                distance_near(from.point_at(t), to, t, tolerance)
+            })
+            .fold(0.0, f64::max)
+    };
+
+    one_way(a, b).max(one_way(b, a))
+}
+
+fn reflect(control: (f64, f64), current: (f64, f64)) -> (f64, f64) {
+    (2.0 * current.0 - control.0, 2.0 * current.1 - control.1)
+}
+
+/// Encodes subpaths as the shortest path data that stays within `tolerance` of the original
+/// geometry. Half of the tolerance goes to moving points (rounding, H/V, S/T, dropped
+/// segments), the other half to replacing segments with lower-degree ones.
+pub fn optimize_path_data(subpaths: &[Subpath], tolerance: f64) -> String {
+    let tolerance = tolerance.max(0.0);
+
+    // rounding both coordinates to d decimals moves a point by at most
+    // sqrt(2) * 0.5 * 10^-d <= tolerance / 2
+    let decimals = (tolerance > 0.0)
+        .then(|| (-(tolerance / 2f64.sqrt()).log10()).ceil().clamp(0.0, 15.0) as usize);
+
+    let mut optimizer = Optimizer {
+        tolerance,
+        decimals,
+        current: (0.0, 0.0),
+        control: None,
+        writer: Writer {
+            out: String::new(),
+            last_command: None,
+            last_number: None,
+        },
+    };
+
+    for subpath in subpaths {
+        optimizer.moveto(subpath.start);
+        optimizer.control = None;
+        let start = optimizer.current;
+
+        let mut segments = subpath.segments.as_slice();
+        if subpath.closed {
+            if let Some((Segment::Line { end, .. }, rest)) = segments.split_last() {
+                if *end == subpath.start {
+                    segments = rest;
+                }
+            }
+        }
+
+        for segment in segments {
+            optimizer.segment(*segment);
+        }
+
+        if subpath.closed {
+            optimizer.writer.push('z', &[]);
+            optimizer.current = start;
+            optimizer.control = None;
+        }
+    }
+
+    optimizer.writer.out
+}
+
+impl Path {
+    /// Shortest path data for this path within `tolerance`, see `optimize_path_data`
+    pub fn optimize(&self, tolerance: f64) -> String {
+        optimize_path_data(&self.subpaths(), tolerance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Points of a segment at `steps` equal steps of its parameter
+    fn samples(segment: &Segment, steps: usize) -> Vec<(f64, f64)> {
+        (0..=steps)
+            .map(|i| segment.point_at(i as f64 / steps as f64))
+            .collect()
+    }
+
+    /// Distance from `point` to a segment, found by refining the closest of its `samples`
+    fn distance_to(point: (f64, f64), segment: &Segment, samples: &[(f64, f64)]) -> f64 {
+        let at = |t: f64| distance(point, segment.point_at(t));
+        let steps = (samples.len() - 1) as f64;
+        let best = (0..samples.len())
+            .min_by(|&i, &j| distance(point, samples[i]).total_cmp(&distance(point, samples[j])))
+            .unwrap_or(0) as f64;
+
+        let (mut low, mut high) = (
+            (best - 1.0).max(0.0) / steps,
+            (best + 1.0).min(steps) / steps,
+        );
+        for _ in 0..60 {
+            let (a, b) = (low + (high - low) / 3.0, high - (high - low) / 3.0);
+            if at(a) < at(b) {
+                high = b;
+            } else {
+                low = a;
+            }
+        }
+        at((low + high) / 2.0)
+    }
+
+    /// Largest distance between the outlines of two paths, both ways, measured from points
+    /// no more than 0.05 apart along each
+    fn max_deviation(a: &Path, b: &Path) -> f64 {
+        let one_way = |from: &Path, to: &Path| {
+            let targets: Vec<(Segment, Vec<(f64, f64)>)> = to
+                .segments()
+                .into_iter()
+                .map(|segment| {
+                    let steps = ((segment.length() / 0.5).ceil() as usize).clamp(64, 4096);
+                    (segment, samples(&segment, steps))
+                })
+                .collect();
+
+            from.segments()
+                .iter()
+                .flat_map(|segment| {
+                    samples(
+                        segment,
+                        ((segment.length() / 0.05).ceil() as usize).clamp(16, 2048),
+                    )
+                })
+                .map(|point| {
+                    targets
+                        .iter()
+                        .map(|(segment, samples)| distance_to(point, segment, samples))
+                        .fold(f64::INFINITY, f64::min)
+                })
+                .fold(0.0, f64::max)
+        };
+
+        one_way(a, b).max(one_way(b, a))
+    }
+
+    fn assert_within(data: &str, tolerance: f64) -> String {
+        let path = Path::init(data);
+        let optimized = path.optimize(tolerance);
+        let deviation = max_deviation(&path, &Path::init(&optimized));
+        assert!(
+            deviation <= tolerance + 1e-4,
+            "{:?} at {} deviates by {}",
+            optimized,
+            tolerance,
+            deviation
+        );
+        optimized
+    }
+
+    #[test]
+    fn keeps_chains_of_short_segments() {
+        let data = format!("M0 0{}", " l.04 0".repeat(100));
+        let optimized = assert_within(&data, 0.1);
+        assert_ne!(optimized, "M0 0");
+
+        let data = format!("M0 0{}", " l.004 0".repeat(7));
+        let optimized = assert_within(&data, 0.01);
+        assert_ne!(optimized, "M0 0");
+    }
+
+    #[test]
+    fn drops_segments_within_tolerance() {
+        assert_eq!(Path::init("M0 0 L0 0 L10 0").optimize(0.01), "M0 0H10");
+    }
+
+    #[test]
+    fn curves_stay_within_tolerance() {
+        assert_within(
+            "M0 0 C 0.001 0 10 5 10 10 S 15 20 20 20 T 40 40 Q 45 45 50 50 T 60 60",
+            0.01,
+        );
+        assert_within(
+            "M1.23456 7.891011 C 3.33333 9.87654 12.3456 4.56789 8.76543 2.1",
+            0.1,
+        );
+        assert_within("M0 0 Q 5.55555 10.4444 10.1234 0.0001 T 20.9876 0.5", 0.05);
+        // nearly a quadratic, replaced by one with room left for rounding
+        assert_within(
+            "M0.005 0.005 C 3.33833 6.70617 6.67167 6.67167 10.005 0.005",
+            0.01,
+        );
+    }
+
+    #[test]
+    fn arcs_stay_within_tolerance() {
+        assert_within("M0 0 A 100 50 37.123456 0 1 150 80", 0.01);
+        assert_within("M0 0 A 100 50 37.123456 1 0 150 80", 0.1);
+        assert_within("M10 10 a 0.3333 0.2222 12.345 0 1 0.5 0.1", 0.001);
+        assert_within("M0 0 A 1000 1000 0 0 1 0.01 10", 0.001);
+        // a rotation rounded to the coordinate decimals turns a large arc too far
+        assert_within("M0 0 A 1000 500 37.123456 0 1 150 80", 0.01);
+        // so eccentric that no rounding of the start point keeps the arc in place
+        assert_within(
+            "M8.45968 9.458 A 465.8985 1.7234 11.417696 0 0 15.52995 0.03617",
+            0.05,
+        );
+        assert_within(
+            "M12.62234 16.35618 A 3.2196 113.8782 6.244145 1 1 .10732 5.64391",
+            0.05,
+        );
+    }
+}
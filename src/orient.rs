@@ -0,0 +1,119 @@
+use crate::{Orientation, Path, Segment, Subpath};
+
+impl Subpath {
+    /// The same subpath drawn from its end to its start, still closed if it was
+    pub fn reversed(&self) -> Subpath {
+        Subpath {
+            start: self.segments.last().map_or(self.start, Segment::end),
+            segments: self.segments.iter().rev().map(Segment::reversed).collect(),
+            closed: self.closed,
+        }
+    }
+
+    /// A point on the subpath away from its joints, used to tell which outlines contain it
+    fn probe(&self) -> Option<(f64, f64)> {
+        self.segments
+            .iter()
+            .find(|segment| segment.length() > 0.0)
+            .map(|segment| segment.point_at(0.5))
+    }
+}
+
+impl Path {
+    /// Every subpath drawn backwards, in the same order; closepaths are kept
+    pub fn reverse(&self) -> Path {
+        let subpaths: Vec<Subpath> = self.subpaths().iter().map(Subpath::reversed).collect();
+        Path::from_subpaths(&subpaths)
+    }
+
+    /// Outer outlines counterclockwise and holes clockwise, as drawn (y down)
+    pub fn make_ccw(&self) -> Path {
+        self.oriented(Orientation::CounterClockwise)
+    }
+
+    /// Outer outlines clockwise and holes counterclockwise, as drawn (y down)
+    pub fn make_cw(&self) -> Path {
+        self.oriented(Orientation::Clockwise)
+    }
+
+    /// Reverses the subpaths that run against `outer`. A subpath inside an odd number of the
+    /// others is a hole and gets the opposite direction; subpaths without area are left as
+    /// they are.
+    fn oriented(&self, outer: Orientation) -> Path {
+        let subpaths = self.subpaths();
+
+        let oriented: Vec<Subpath> = subpaths
+            .iter()
+            .enumerate()
+            .map(|(index, subpath)| {
+                let Some(current) = subpath.orientation() else {
+                    return subpath.clone();
+                };
+
+                let depth = subpath.probe().map_or(0, |point| {
+                    subpaths
+                        .iter()
+                        .enumerate()
+                        .filter(|(other, candidate)| {
+                            *other != index && candidate.winding_number(point) != 0
+                        })
+                        .count()
+                });
+
+                let wanted = match (outer, depth % 2 == 1) {
+                    (orientation, false) => orientation,
+                    (Orientation::Clockwise, true) => Orientation::CounterClockwise,
+                    (Orientation::CounterClockwise, true) => Orientation::Clockwise,
+                };
+
+                if current == wanted {
+                    subpath.clone()
+                } else {
+                    subpath.reversed()
+                }
+            })
+            .collect();
+
+        Path::from_subpaths(&oriented)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn orientations(path: &Path) -> Vec<Option<Orientation>> {
+        path.subpaths().iter().map(Subpath::orientation).collect()
+    }
+
+    #[test]
+    fn holes_run_against_their_outline() {
+        // both drawn clockwise
+        let ring = Path::init("M0 0 H20 V20 H0 Z M5 5 H15 V15 H5 Z");
+
+        assert_eq!(
+            orientations(&ring.make_ccw()),
+            [
+                Some(Orientation::CounterClockwise),
+                Some(Orientation::Clockwise)
+            ]
+        );
+        assert_eq!(
+            orientations(&ring.make_cw()),
+            [
+                Some(Orientation::Clockwise),
+                Some(Orientation::CounterClockwise)
+            ]
+        );
+    }
+
+    #[test]
+    fn reversing_twice_gives_the_path_back() {
+        let path = Path::init("M0 0 C0 10 10 10 10 0 L20 0 Z M30 0 A5 5 0 0 1 40 0");
+        let reversed = path.reverse();
+
+        assert_eq!(reversed.subpaths()[0].start, (0.0, 0.0));
+        assert_eq!(reversed.subpaths()[1].start, (40.0, 0.0));
+        assert_eq!(reversed.reverse().to_path_data(), path.to_path_data());
+    }
+}
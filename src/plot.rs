@@ -0,0 +1,137 @@
+use crate::{CoordinateSystem, Path, Segment};
+use plotly::common::{DashType, Line, Marker, Mode, Title};
+use plotly::layout::{Axis, Layout};
+use plotly::{Plot, Scatter};
+use std::fs::File;
+use std::io::Write;
+
+/// Flattening tolerance for drawing subpath outlines, in path units
+const OUTLINE_TOLERANCE: f64 = 0.05;
+
+/// What `Path::plot` draws besides the axes
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlotOptions {
+    pub title: String,
+    /// sampled points from `get_points`
+    pub points: bool,
+    /// one line trace per subpath
+    pub subpaths: bool,
+    /// dashed polygons through the control points of Bézier segments
+    pub control_polygons: bool,
+}
+
+impl Default for PlotOptions {
+    fn default() -> Self {
+        PlotOptions {
+            title: "Point Plot".to_string(),
+            points: true,
+            subpaths: true,
+            control_polygons: false,
+        }
+    }
+}
+
+/// Coordinate lists of points in path coordinates, flipped into the cartesian system of
+/// `points` so outlines and samples line up
+fn unzip(points: &[(f64, f64)]) -> (Vec<f64>, Vec<f64>) {
+    points
+        .iter()
+        .map(|&point| CoordinateSystem::CARTESIAN.map(point))
+        .unzip()
+}
+
+impl Path {
+    /// Builds a plotly figure of the path with the equal aspect ratio and grid that
+    /// `plotter.py` uses. Points are only drawn once `get_points` has run.
+    pub fn plot(&self, options: &PlotOptions) -> Plot {
+        let mut plot = Plot::new();
+
+        if options.subpaths {
+            for (i, subpath) in self.subpaths().iter().enumerate() {
+                let (x, y) = unzip(&subpath.flatten(OUTLINE_TOLERANCE));
+                let trace = Scatter::new(x, y)
+                    .mode(Mode::Lines)
+                    .name(format!("Subpath {}", i))
+                    .line(Line::new().color("black").width(1.0));
+                plot.add_trace(trace);
+            }
+        }
+
+        if options.control_polygons {
+            let mut x = vec![];
+            let mut y = vec![];
+
+            for segment in self.segments() {
+                let polygon = match segment {
+                    Segment::Quadratic {
+                        start,
+                        control,
+                        end,
+                    } => vec![start, control, end],
+                    Segment::Cubic {
+                        start,
+                        control1,
+                        control2,
+                        end,
+                    } => vec![start, control1, control2, end],
+                    _ => continue,
+                };
+
+                // a gap between polygons keeps them from being connected
+                let (xs, ys) = unzip(&polygon);
+                x.extend(xs.into_iter().map(Some));
+                y.extend(ys.into_iter().map(Some));
+                x.push(None);
+                y.push(None);
+            }
+
+            let trace = Scatter::new(x, y)
+                .mode(Mode::LinesMarkers)
+                .name("Control polygons")
+                .line(Line::new().color("gray").dash(DashType::Dash).width(1.0))
+                .marker(Marker::new().color("gray").size(5));
+            plot.add_trace(trace);
+        }
+
+        if options.points && !self.points.is_empty() {
+            // `points` are already cartesian
+            let (x, y): (Vec<f64>, Vec<f64>) = self.points.iter().copied().unzip();
+            let trace = Scatter::new(x, y)
+                .mode(Mode::Markers)
+                .name("Points")
+                .marker(Marker::new().color("blue").size(6).opacity(0.6));
+            plot.add_trace(trace);
+        }
+
+        let layout = Layout::new()
+            .title(Title::with_text(&options.title))
+            .x_axis(
+                Axis::new()
+                    .title(Title::with_text("X Coordinate"))
+                    .show_grid(true)
+                    .zero_line(true),
+            )
+            .y_axis(
+                Axis::new()
+                    .title(Title::with_text("Y Coordinate"))
+                    .show_grid(true)
+                    .zero_line(true)
+                    // one y unit as long as one x unit, as set_aspect('equal') in plotter.py
+                    .scale_anchor("x"),
+            );
+        plot.set_layout(layout);
+
+        plot
+    }
+
+    /// Writes the plot as a standalone HTML file with plotly.js embedded
+    pub fn save_html_plot(&self, filename: &str, options: &PlotOptions) -> std::io::Result<()> {
+        let mut plot = self.plot(options);
+        plot.use_local_plotly();
+
+        let mut file = File::create(filename)?;
+        file.write_all(plot.to_html().as_bytes())?;
+        file.flush()
+    }
+}
@@ -0,0 +1,135 @@
+use crate::sample::LengthTable;
+use crate::{Path, Sample};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::{Distribution, Normal};
+use std::fmt;
+
+/// Bins used to tabulate the cumulative distribution of a user density
+const DENSITY_BINS: usize = 1024;
+
+impl Path {
+    /// `count` points distributed uniformly by arc length, sorted along the path.
+    /// The same `seed` always gives the same points.
+    pub fn random_samples(&self, count: usize, seed: u64) -> Vec<Sample> {
+        self.random_samples_with_density(count, |_| 1.0, seed)
+    }
+
+    /// `count` points drawn with probability proportional to `density(length)`, where
+    /// `length` is the arc length from the start of the path. Negative densities count as 0.
+    pub fn random_samples_with_density<F>(&self, count: usize, density: F, seed: u64) -> Vec<Sample>
+    where
+        F: Fn(f64) -> f64,
+    {
+        let table = LengthTable::new(&self.subpaths());
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        if table.total <= 0.0 {
+            return vec![];
+        }
+
+        // cumulative distribution over equal length bins, integrated with the trapezoid rule
+        let step = table.total / DENSITY_BINS as f64;
+        let mut cumulative = vec![0.0; DENSITY_BINS + 1];
+        for i in 0..DENSITY_BINS {
+            let a = density(i as f64 * step).max(0.0);
+            let b = density((i + 1) as f64 * step).max(0.0);
+            cumulative[i + 1] = cumulative[i] + (a + b) / 2.0 * step;
+        }
+
+        let mass = cumulative[DENSITY_BINS];
+        if mass <= 0.0 {
+            return vec![];
+        }
+
+        let mut lengths: Vec<f64> = (0..count)
+            .map(|_| {
+                let target = rng.random::<f64>() * mass;
+                let bin = cumulative
+                    .partition_point(|&value| value <= target)
+                    .clamp(1, DENSITY_BINS);
+
+                // linear interpolation inside the bin
+                let (low, high) = (cumulative[bin - 1], cumulative[bin]);
+                let fraction = if high > low {
+                    (target - low) / (high - low)
+                } else {
+                    0.5
+                };
+                (bin as f64 - 1.0 + fraction) * step
+            })
+            .collect();
+        lengths.sort_by(f64::total_cmp);
+
+        lengths
+            .into_iter()
+            .filter_map(|length| table.sample_at_length(length))
+            .collect()
+    }
+}
+
+/// `jitter_samples` was given a standard deviation that is not a finite number
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InvalidStdDev(pub f64);
+
+impl fmt::Display for InvalidStdDev {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "standard deviation {} is not a finite number", self.0)
+    }
+}
+
+impl std::error::Error for InvalidStdDev {}
+
+/// Moves every sample along its normal by a normally distributed offset with standard
+/// deviation `std_dev`. Arc length, `t` and tangent still describe the original point.
+/// Fails without moving anything when `std_dev` is NaN or infinite.
+pub fn jitter_samples(
+    samples: &mut [Sample],
+    std_dev: f64,
+    seed: u64,
+) -> Result<(), InvalidStdDev> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let normal = Normal::new(0.0, std_dev.abs()).map_err(|_| InvalidStdDev(std_dev))?;
+
+    for sample in samples {
+        let offset: f64 = normal.sample(&mut rng);
+        let (tx, ty) = sample.tangent;
+
+        // left-hand normal of the drawing direction
+        sample.point.0 -= ty * offset;
+        sample.point.1 += tx * offset;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jitter_moves_along_the_normal() {
+        let path = Path::init("M0 0 H10");
+        let mut samples = path.random_samples(20, 7);
+        let original = samples.clone();
+
+        jitter_samples(&mut samples, 0.5, 7).unwrap();
+        for (moved, sample) in samples.iter().zip(&original) {
+            assert_eq!(moved.point.0, sample.point.0);
+            assert_ne!(moved.point.1, sample.point.1);
+        }
+        assert_eq!(samples.len(), 20);
+    }
+
+    #[test]
+    fn jitter_rejects_non_finite_deviations() {
+        let mut samples = Path::init("M0 0 H10").random_samples(5, 1);
+        let original = samples.clone();
+
+        for std_dev in [f64::NAN, f64::INFINITY] {
+            let error = jitter_samples(&mut samples, std_dev, 1).unwrap_err();
+            assert!(error.0.is_nan() || error.0 == std_dev);
+        }
+        assert_eq!(samples, original);
+    }
+}
@@ -0,0 +1,408 @@
+use crate::segment::distance_to_line_segment;
+use crate::Path;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+/// Vertical samples per pixel row, horizontal coverage is computed exactly
+const SUBSAMPLES: usize = 16;
+
+/// Flattening tolerance in pixels
+const FLATTEN_TOLERANCE: f64 = 0.1;
+
+/// Polygon edge in pixel space as (top, bottom, winding direction)
+type Edge = ((f64, f64), (f64, f64), i32);
+
+/// How the inside of a self-overlapping or nested outline is decided
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FillRule {
+    NonZero,
+    EvenOdd,
+}
+
+impl FillRule {
+    /// Whether a point with the given winding number is inside
+    pub fn is_inside(&self, winding: i32) -> bool {
+        match self {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
+/// Maps path coordinates to pixels: pixel = (point - origin) * scale
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Viewport {
+    pub origin: (f64, f64),
+    pub scale: f64,
+}
+
+impl Viewport {
+    /// Scales and centers `path` into a `width` x `height` image, keeping `margin` pixels free
+    pub fn fit(path: &Path, width: usize, height: usize, margin: f64) -> Self {
+        let Some((min, max)) = path.bounding_box() else {
+            return Viewport {
+                origin: (0.0, 0.0),
+                scale: 1.0,
+            };
+        };
+
+        let available = (
+            (width as f64 - 2.0 * margin).max(1.0),
+            (height as f64 - 2.0 * margin).max(1.0),
+        );
+        let size = ((max.0 - min.0).max(1e-9), (max.1 - min.1).max(1e-9));
+        let scale = (available.0 / size.0).min(available.1 / size.1);
+
+        // center the drawing in the image
+        Viewport {
+            origin: (
+                min.0 - (width as f64 / scale - size.0) / 2.0,
+                min.1 - (height as f64 / scale - size.1) / 2.0,
+            ),
+            scale,
+        }
+    }
+
+    pub fn to_pixel(&self, point: (f64, f64)) -> (f64, f64) {
+        (
+            (point.0 - self.origin.0) * self.scale,
+            (point.1 - self.origin.1) * self.scale,
+        )
+    }
+}
+
+/// 8-bit grayscale image, 255 is white. Paths are drawn in black.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bitmap {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+}
+
+impl Bitmap {
+    /// White image of the given size
+    pub fn new(width: usize, height: usize) -> Self {
+        Bitmap {
+            width,
+            height,
+            pixels: vec![255; width * height],
+        }
+    }
+
+    /// Fills the interior of `path` with anti-aliased edges. Open subpaths are closed
+    /// implicitly, as SVG does when filling.
+    pub fn fill_path(&mut self, path: &Path, rule: FillRule, viewport: &Viewport) {
+        let mut edges: Vec<Edge> = vec![];
+
+        for subpath in path.subpaths() {
+            let mut points: Vec<(f64, f64)> = subpath
+                .flatten(FLATTEN_TOLERANCE / viewport.scale)
+                .into_iter()
+                .map(|point| viewport.to_pixel(point))
+                .collect();
+            points.push(points[0]);
+
+            for pair in points.windows(2) {
+                let (a, b) = (pair[0], pair[1]);
+                if a.1 < b.1 {
+                    edges.push((a, b, 1));
+                } else if a.1 > b.1 {
+                    edges.push((b, a, -1));
+                }
+            }
+        }
+
+        let mut coverage = vec![0.0; self.width * self.height];
+        let mut row = vec![0.0; self.width + 1];
+
+        for y in 0..self.height {
+            row.iter_mut().for_each(|value| *value = 0.0);
+
+            for sample in 0..SUBSAMPLES {
+                let scan_y = y as f64 + (sample as f64 + 0.5) / SUBSAMPLES as f64;
+
+                // crossings of this scanline sorted left to right
+                let mut crossings: Vec<(f64, i32)> = edges
+                    .iter()
+                    .filter(|(top, bottom, _)| top.1 <= scan_y && scan_y < bottom.1)
+                    .map(|(top, bottom, direction)| {
+                        let t = (scan_y - top.1) / (bottom.1 - top.1);
+                        (top.0 + (bottom.0 - top.0) * t, *direction)
+                    })
+                    .collect();
+                crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+                let mut winding = 0;
+                for pair in crossings.windows(2) {
+                    winding += pair[0].1;
+                    if rule.is_inside(winding) {
+                        add_span(&mut row, pair[0].0, pair[1].0, 1.0 / SUBSAMPLES as f64);
+                    }
+                }
+            }
+
+            coverage[y * self.width..(y + 1) * self.width].copy_from_slice(&row[..self.width]);
+        }
+
+        self.composite(&coverage);
+    }
+
+    /// Draws the outline of `path` with a stroke `width` in path units
+    pub fn stroke_path(&mut self, path: &Path, width: f64, viewport: &Viewport) {
+        let half_width = width * viewport.scale / 2.0;
+        let mut coverage = vec![0.0_f64; self.width * self.height];
+
+        for subpath in path.subpaths() {
+            let points: Vec<(f64, f64)> = subpath
+                .flatten(FLATTEN_TOLERANCE / viewport.scale)
+                .into_iter()
+                .map(|point| viewport.to_pixel(point))
+                .collect();
+
+            for pair in points.windows(2) {
+                let (a, b) = (pair[0], pair[1]);
+                let reach = half_width + 1.0;
+
+                let x0 = (a.0.min(b.0) - reach).floor().max(0.0) as usize;
+                let x1 = ((a.0.max(b.0) + reach).ceil().max(0.0) as usize).min(self.width);
+                let y0 = (a.1.min(b.1) - reach).floor().max(0.0) as usize;
+                let y1 = ((a.1.max(b.1) + reach).ceil().max(0.0) as usize).min(self.height);
+
+                for y in y0..y1 {
+                    for x in x0..x1 {
+                        let center = (x as f64 + 0.5, y as f64 + 0.5);
+                        let distance = distance_to_line_segment(center, a, b);
+
+                        // one pixel wide linear ramp along the stroke edge
+                        let value = (half_width + 0.5 - distance).clamp(0.0, 1.0);
+                        let pixel = &mut coverage[y * self.width + x];
+                        *pixel = pixel.max(value);
+                    }
+                }
+            }
+        }
+
+        self.composite(&coverage);
+    }
+
+    /// Darkens every pixel by its coverage in [0, 1]
+    fn composite(&mut self, coverage: &[f64]) {
+        for (pixel, value) in self.pixels.iter_mut().zip(coverage) {
+            let value = value.clamp(0.0, 1.0);
+            *pixel = (*pixel as f64 * (1.0 - value)).round() as u8;
+        }
+    }
+
+    /// Writes a binary PGM (P5) image
+    pub fn write_pgm<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        write!(writer, "P5\n{} {}\n255\n", self.width, self.height)?;
+        writer.write_all(&self.pixels)
+    }
+
+    pub fn save_pgm(&self, filename: &str) -> std::io::Result<()> {
+        let mut file = BufWriter::new(File::create(filename)?);
+        self.write_pgm(&mut file)?;
+        file.flush()
+    }
+
+    /// Writes an 8-bit grayscale PNG. The image data is stored without compression, which
+    /// keeps the encoder dependency free.
+    pub fn write_png<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(&[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'])?;
+
+        let mut header = vec![];
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        // bit depth 8, grayscale, deflate, adaptive filtering, no interlace
+        header.extend_from_slice(&[8, 0, 0, 0, 0]);
+        write_png_chunk(writer, b"IHDR", &header)?;
+
+        // every scanline starts with filter type 0 (none)
+        let mut raw = Vec::with_capacity((self.width + 1) * self.height);
+        for row in self.pixels.chunks(self.width.max(1)) {
+            raw.push(0);
+            raw.extend_from_slice(row);
+        }
+
+        // zlib stream made of stored deflate blocks
+        let mut data = vec![0x78, 0x01];
+        let blocks: Vec<&[u8]> = raw.chunks(65535).collect();
+        for (i, block) in blocks.iter().enumerate() {
+            data.push((i == blocks.len() - 1) as u8);
+            data.extend_from_slice(&(block.len() as u16).to_le_bytes());
+            data.extend_from_slice(&(!(block.len() as u16)).to_le_bytes());
+            data.extend_from_slice(block);
+        }
+        if blocks.is_empty() {
+            data.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+        }
+        data.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+        write_png_chunk(writer, b"IDAT", &data)?;
+        write_png_chunk(writer, b"IEND", &[])
+    }
+
+    pub fn save_png(&self, filename: &str) -> std::io::Result<()> {
+        let mut file = BufWriter::new(File::create(filename)?);
+        self.write_png(&mut file)?;
+        file.flush()
+    }
+}
+
+/// Adds `value` times the covered part of [x0, x1] to each pixel of the row
+fn add_span(row: &mut [f64], x0: f64, x1: f64, value: f64) {
+    let width = (row.len() - 1) as f64;
+    let (x0, x1) = (x0.clamp(0.0, width), x1.clamp(0.0, width));
+
+    if x1 <= x0 {
+        return;
+    }
+
+    let first = x0.floor() as usize;
+    let last = x1.floor() as usize;
+
+    if first == last {
+        row[first] += (x1 - x0) * value;
+        return;
+    }
+
+    row[first] += (first as f64 + 1.0 - x0) * value;
+    for pixel in &mut row[first + 1..last] {
+        *pixel += value;
+    }
+    row[last] += (x1 - last as f64) * value;
+}
+
+fn write_png_chunk<W: Write>(writer: &mut W, kind: &[u8; 4], data: &[u8]) -> std::io::Result<()> {
+    writer.write_all(&(data.len() as u32).to_be_bytes())?;
+    writer.write_all(kind)?;
+    writer.write_all(data)?;
+
+    let mut crc_input = kind.to_vec();
+    crc_input.extend_from_slice(data);
+    writer.write_all(&crc32(&crc_input).to_be_bytes())
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffff_u32;
+
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1_u32, 0_u32);
+
+    for &byte in bytes {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+
+    (b << 16) | a
+}
+
+impl Path {
+    /// Renders the filled path, scaled to fit a `width` x `height` image
+    pub fn rasterize(&self, width: usize, height: usize, rule: FillRule) -> Bitmap {
+        let mut bitmap = Bitmap::new(width, height);
+        let viewport = Viewport::fit(self, width, height, 2.0);
+        bitmap.fill_path(self, rule, &viewport);
+        bitmap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PIXELS: Viewport = Viewport {
+        origin: (0.0, 0.0),
+        scale: 1.0,
+    };
+
+    fn fill(data: &str, size: usize, rule: FillRule) -> Vec<u8> {
+        let mut bitmap = Bitmap::new(size, size);
+        bitmap.fill_path(&Path::init(data), rule, &PIXELS);
+        bitmap.pixels
+    }
+
+    fn stroke(data: &str, size: usize, width: f64) -> Vec<u8> {
+        let mut bitmap = Bitmap::new(size, size);
+        bitmap.stroke_path(&Path::init(data), width, &PIXELS);
+        bitmap.pixels
+    }
+
+    #[test]
+    fn fills_pixel_aligned_square() {
+        #[rustfmt::skip]
+        let expected = vec![
+            255, 255, 255, 255,
+            255,   0,   0, 255,
+            255,   0,   0, 255,
+            255, 255, 255, 255,
+        ];
+        assert_eq!(fill("M1 1 H3 V3 H1 Z", 4, FillRule::NonZero), expected);
+    }
+
+    #[test]
+    fn fills_partial_pixels_by_coverage() {
+        #[rustfmt::skip]
+        let expected = vec![
+            255, 255, 255, 255,
+            128,   0,   0, 255,
+            128,   0,   0, 255,
+            255, 255, 255, 255,
+        ];
+        assert_eq!(fill("M0.5 1 H3 V3 H0.5 Z", 4, FillRule::NonZero), expected);
+    }
+
+    #[test]
+    fn fill_rules_differ_on_nested_outlines() {
+        let data = "M0 0 H4 V4 H0 Z M1 1 H3 V3 H1 Z";
+
+        assert_eq!(fill(data, 4, FillRule::NonZero), vec![0; 16]);
+
+        #[rustfmt::skip]
+        let expected = vec![
+            0,   0,   0, 0,
+            0, 255, 255, 0,
+            0, 255, 255, 0,
+            0,   0,   0, 0,
+        ];
+        assert_eq!(fill(data, 4, FillRule::EvenOdd), expected);
+    }
+
+    #[test]
+    fn strokes_horizontal_line() {
+        #[rustfmt::skip]
+        let expected = vec![
+            255, 255, 255, 255,
+            128, 128, 128, 128,
+            128, 128, 128, 128,
+            255, 255, 255, 255,
+        ];
+        assert_eq!(stroke("M0 2 H4", 4, 1.0), expected);
+
+        #[rustfmt::skip]
+        let expected = vec![
+            255, 255, 255, 255,
+              0,   0,   0,   0,
+              0,   0,   0,   0,
+            255, 255, 255, 255,
+        ];
+        assert_eq!(stroke("M0 2 H4", 4, 2.0), expected);
+    }
+}
@@ -0,0 +1,262 @@
+use crate::{CoordinateSystem, Path, Segment, Subpath};
+
+/// A point on the path together with where it came from. Coordinates, tangents and
+/// curvature are cartesian (y up), like `Path::points`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sample {
+    pub point: (f64, f64),
+    /// index into `Path::subpaths`
+    pub subpath: usize,
+    /// index into the segments of that subpath
+    pub segment: usize,
+    /// parameter on the segment in [0, 1]
+    pub t: f64,
+    /// arc length from the start of the path
+    pub length: f64,
+    /// unit tangent in drawing direction
+    pub tangent: (f64, f64),
+    pub curvature: f64,
+}
+
+/// A per-sample quantity, used to pick columns for matrix and table output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SampleColumn {
+    X,
+    Y,
+    Length,
+    T,
+    Segment,
+    Subpath,
+    TangentX,
+    TangentY,
+    Curvature,
+}
+
+impl SampleColumn {
+    pub fn value(&self, sample: &Sample) -> f64 {
+        match self {
+            SampleColumn::X => sample.point.0,
+            SampleColumn::Y => sample.point.1,
+            SampleColumn::Length => sample.length,
+            SampleColumn::T => sample.t,
+            SampleColumn::Segment => sample.segment as f64,
+            SampleColumn::Subpath => sample.subpath as f64,
+            SampleColumn::TangentX => sample.tangent.0,
+            SampleColumn::TangentY => sample.tangent.1,
+            SampleColumn::Curvature => sample.curvature,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SampleColumn::X => "x",
+            SampleColumn::Y => "y",
+            SampleColumn::Length => "length",
+            SampleColumn::T => "t",
+            SampleColumn::Segment => "segment",
+            SampleColumn::Subpath => "subpath",
+            SampleColumn::TangentX => "tangent_x",
+            SampleColumn::TangentY => "tangent_y",
+            SampleColumn::Curvature => "curvature",
+        }
+    }
+
+    /// The column called `name`, as written by `name`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "x" => Some(SampleColumn::X),
+            "y" => Some(SampleColumn::Y),
+            "length" => Some(SampleColumn::Length),
+            "t" => Some(SampleColumn::T),
+            "segment" => Some(SampleColumn::Segment),
+            "subpath" => Some(SampleColumn::Subpath),
+            "tangent_x" => Some(SampleColumn::TangentX),
+            "tangent_y" => Some(SampleColumn::TangentY),
+            "curvature" => Some(SampleColumn::Curvature),
+            _ => None,
+        }
+    }
+}
+
+/// Evaluates `segment` at `t` and maps the result into the cartesian system, as
+/// `transform_svg_coordinates_to_cartesian` does
+pub(crate) fn sample_at(
+    segment: &Segment,
+    subpath: usize,
+    index: usize,
+    t: f64,
+    length: f64,
+) -> Sample {
+    let system = CoordinateSystem::CARTESIAN;
+
+    Sample {
+        point: system.map(segment.point_at(t)),
+        subpath,
+        segment: index,
+        t,
+        length,
+        tangent: system.map_direction(segment.tangent(t)),
+        // mirroring turns left turns into right turns
+        curvature: -segment.curvature(t),
+    }
+}
+
+/// Total arc length of all subpaths
+pub(crate) fn total_length(subpaths: &[Subpath]) -> f64 {
+    subpaths
+        .iter()
+        .flat_map(|subpath| &subpath.segments)
+        .map(Segment::length)
+        .sum()
+}
+
+/// Cumulative arc lengths of every segment, used to turn a distance along the whole path
+/// into a segment and parameter
+pub(crate) struct LengthTable {
+    /// (subpath index, segment index, segment, length before it, its length)
+    entries: Vec<(usize, usize, Segment, f64, f64)>,
+    pub total: f64,
+}
+
+impl LengthTable {
+    pub fn new(subpaths: &[Subpath]) -> Self {
+        let mut entries = vec![];
+        let mut total = 0.0;
+
+        for (subpath_index, subpath) in subpaths.iter().enumerate() {
+            for (index, segment) in subpath.segments.iter().enumerate() {
+                let length = segment.length();
+                entries.push((subpath_index, index, *segment, total, length));
+                total += length;
+            }
+        }
+
+        LengthTable { entries, total }
+    }
+
+    /// Segment entry and parameter at `length` from the start of the path
+    pub fn locate(&self, length: f64) -> Option<(usize, usize, Segment, f64)> {
+        let length = length.clamp(0.0, self.total);
+
+        // last segment with non-zero length that starts at or before `length`
+        let end = self.entries.partition_point(|entry| entry.3 <= length);
+        let (subpath, index, segment, offset, _) = self.entries[..end]
+            .iter()
+            .rev()
+            .find(|entry| entry.4 > 0.0)
+            .or(self.entries.first())?;
+
+        Some((
+            *subpath,
+            *index,
+            *segment,
+            segment.parameter_at_length(length - offset),
+        ))
+    }
+
+    /// Exact pieces of the path between two distances from its start, one subpath for every
+    /// subpath the range touches. An empty range gives a single zero-length line, so dots of
+    /// zero-length dashes can still be capped.
+    pub fn slice(&self, start: f64, end: f64) -> Vec<Subpath> {
+        let (start, end) = (start.clamp(0.0, self.total), end.clamp(0.0, self.total));
+
+        if start > end {
+            return vec![];
+        }
+        if start == end {
+            let Some((_, _, segment, t)) = self.locate(start) else {
+                return vec![];
+            };
+            let point = segment.point_at(t);
+
+            return vec![Subpath {
+                start: point,
+                segments: vec![Segment::Line {
+                    start: point,
+                    end: point,
+                }],
+                closed: false,
+            }];
+        }
+
+        let mut subpaths: Vec<(usize, Subpath)> = vec![];
+
+        for &(subpath_index, _, segment, offset, length) in &self.entries {
+            if length == 0.0 || offset + length <= start || offset >= end {
+                continue;
+            }
+
+            let t0 = if start > offset {
+                segment.parameter_at_length(start - offset)
+            } else {
+                0.0
+            };
+            let t1 = if end < offset + length {
+                segment.parameter_at_length(end - offset)
+            } else {
+                1.0
+            };
+            let piece = segment.subsegment(t0, t1);
+
+            match subpaths.last_mut() {
+                Some((index, subpath)) if *index == subpath_index => subpath.segments.push(piece),
+                _ => subpaths.push((
+                    subpath_index,
+                    Subpath {
+                        start: piece.start(),
+                        segments: vec![piece],
+                        closed: false,
+                    },
+                )),
+            }
+        }
+
+        subpaths.into_iter().map(|(_, subpath)| subpath).collect()
+    }
+
+    pub fn sample_at_length(&self, length: f64) -> Option<Sample> {
+        let length = length.clamp(0.0, self.total);
+        let (subpath, index, segment, t) = self.locate(length)?;
+
+        Some(sample_at(&segment, subpath, index, t, length))
+    }
+}
+
+impl Path {
+    /// Samples `n` points along the path the same way `get_points` does: every segment gets
+    /// a share of the points proportional to its length, spread evenly in `t`. Unlike
+    /// `points`, samples keep drawing order and joints between segments appear once.
+    pub fn samples(&self) -> Vec<Sample> {
+        let subpaths = self.subpaths();
+        let total_length = total_length(&subpaths);
+        let mut samples = vec![];
+        let mut offset = 0.0;
+
+        for (subpath_index, subpath) in subpaths.iter().enumerate() {
+            for (index, segment) in subpath.segments.iter().enumerate() {
+                let segment_length = segment.length();
+
+                // calculate n, (n_length)/(total_length)
+                let n = if total_length > 0.0 {
+                    (self.n * segment_length / total_length).round().max(1.0) as usize
+                } else {
+                    1
+                };
+
+                let first = if index == 0 { 0 } else { 1 };
+
+                for i in first..=n {
+                    let t = i as f64 / n as f64;
+                    let length = offset + segment.length_between(0.0, t);
+                    samples.push(sample_at(segment, subpath_index, index, t, length));
+                }
+
+                offset += segment_length;
+            }
+        }
+
+        samples
+    }
+}
@@ -0,0 +1,953 @@
+use crate::{cubic_bezier_arc_length, quadratic_bezier_arc_length, Path};
+use std::f64::consts::PI;
+
+/// Gaussian quadrature points and weights for n=7, exact for polynomials up to degree 13
+pub(crate) const GAUSS_POINTS: [(f64, f64); 7] = [
+    (-0.949107912342759, 0.129484966168870),
+    (-0.741531185599394, 0.279705391489277),
+    (-0.405845151377397, 0.381830050505119),
+    (0.000000000000000, 0.417959183673469),
+    (0.405845151377397, 0.381830050505119),
+    (0.741531185599394, 0.279705391489277),
+    (0.949107912342759, 0.129484966168870),
+];
+
+/// A single drawing command of a path, resolved to absolute SVG coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "lowercase"))]
+pub enum Segment {
+    Line {
+        start: (f64, f64),
+        end: (f64, f64),
+    },
+    Quadratic {
+        start: (f64, f64),
+        control: (f64, f64),
+        end: (f64, f64),
+    },
+    Cubic {
+        start: (f64, f64),
+        control1: (f64, f64),
+        control2: (f64, f64),
+        end: (f64, f64),
+    },
+    /// Elliptical arc in SVG endpoint form, `rotation` is in degrees
+    Arc {
+        start: (f64, f64),
+        radii: (f64, f64),
+        rotation: f64,
+        large_arc: bool,
+        sweep: bool,
+        end: (f64, f64),
+    },
+}
+
+/// A run of connected segments starting at a moveto, optionally closed with Z
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Subpath {
+    pub start: (f64, f64),
+    pub segments: Vec<Segment>,
+    pub closed: bool,
+}
+
+/// Center parameterization of an arc, angles in radians
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ArcCenter {
+    pub center: (f64, f64),
+    pub radii: (f64, f64),
+    pub rotation: f64,
+    pub start_angle: f64,
+    pub sweep_angle: f64,
+}
+
+impl ArcCenter {
+    pub fn point_at_angle(&self, angle: f64) -> (f64, f64) {
+        let (sin_phi, cos_phi) = self.rotation.sin_cos();
+        let x = self.radii.0 * angle.cos();
+        let y = self.radii.1 * angle.sin();
+
+        (
+            self.center.0 + cos_phi * x - sin_phi * y,
+            self.center.1 + sin_phi * x + cos_phi * y,
+        )
+    }
+}
+
+impl Segment {
+    pub fn start(&self) -> (f64, f64) {
+        match *self {
+            Segment::Line { start, .. }
+            | Segment::Quadratic { start, .. }
+            | Segment::Cubic { start, .. }
+            | Segment::Arc { start, .. } => start,
+        }
+    }
+
+    pub fn end(&self) -> (f64, f64) {
+        match *self {
+            Segment::Line { end, .. }
+            | Segment::Quadratic { end, .. }
+            | Segment::Cubic { end, .. }
+            | Segment::Arc { end, .. } => end,
+        }
+    }
+
+    /// Position at parameter `t` in [0, 1]
+    pub fn point_at(&self, t: f64) -> (f64, f64) {
+        let mt = 1.0 - t;
+
+        match *self {
+            Segment::Line { start, end } => lerp(start, end, t),
+            Segment::Quadratic {
+                start,
+                control,
+                end,
+            } => (
+                mt * mt * start.0 + 2.0 * mt * t * control.0 + t * t * end.0,
+                mt * mt * start.1 + 2.0 * mt * t * control.1 + t * t * end.1,
+            ),
+            Segment::Cubic {
+                start,
+                control1,
+                control2,
+                end,
+            } => (
+                mt.powi(3) * start.0
+                    + 3.0 * mt.powi(2) * t * control1.0
+                    + 3.0 * mt * t.powi(2) * control2.0
+                    + t.powi(3) * end.0,
+                mt.powi(3) * start.1
+                    + 3.0 * mt.powi(2) * t * control1.1
+                    + 3.0 * mt * t.powi(2) * control2.1
+                    + t.powi(3) * end.1,
+            ),
+            Segment::Arc { start, end, .. } => match self.arc_center() {
+                Some(arc) => {
+                    if t <= 0.0 {
+                        start
+                    } else if t >= 1.0 {
+                        end
+                    } else {
+                        arc.point_at_angle(arc.start_angle + arc.sweep_angle * t)
+                    }
+                }
+                None => lerp(start, end, t),
+            },
+        }
+    }
+
+    /// First derivative with respect to `t`
+    pub fn derivative(&self, t: f64) -> (f64, f64) {
+        let mt = 1.0 - t;
+
+        match *self {
+            Segment::Line { start, end } => (end.0 - start.0, end.1 - start.1),
+            Segment::Quadratic {
+                start,
+                control,
+                end,
+            } => (
+                2.0 * (mt * (control.0 - start.0) + t * (end.0 - control.0)),
+                2.0 * (mt * (control.1 - start.1) + t * (end.1 - control.1)),
+            ),
+            Segment::Cubic {
+                start,
+                control1,
+                control2,
+                end,
+            } => (
+                3.0 * mt * mt * (control1.0 - start.0)
+                    + 6.0 * mt * t * (control2.0 - control1.0)
+                    + 3.0 * t * t * (end.0 - control2.0),
+                3.0 * mt * mt * (control1.1 - start.1)
+                    + 6.0 * mt * t * (control2.1 - control1.1)
+                    + 3.0 * t * t * (end.1 - control2.1),
+            ),
+            Segment::Arc { start, end, .. } => match self.arc_center() {
+                Some(arc) => {
+                    let angle = arc.start_angle + arc.sweep_angle * t;
+                    let (sin_phi, cos_phi) = arc.rotation.sin_cos();
+                    let dx = -arc.radii.0 * angle.sin() * arc.sweep_angle;
+                    let dy = arc.radii.1 * angle.cos() * arc.sweep_angle;
+
+                    (cos_phi * dx - sin_phi * dy, sin_phi * dx + cos_phi * dy)
+                }
+                None => (end.0 - start.0, end.1 - start.1),
+            },
+        }
+    }
+
+    /// Second derivative with respect to `t`
+    pub fn second_derivative(&self, t: f64) -> (f64, f64) {
+        match *self {
+            Segment::Line { .. } => (0.0, 0.0),
+            Segment::Quadratic {
+                start,
+                control,
+                end,
+            } => (
+                2.0 * (start.0 - 2.0 * control.0 + end.0),
+                2.0 * (start.1 - 2.0 * control.1 + end.1),
+            ),
+            Segment::Cubic {
+                start,
+                control1,
+                control2,
+                end,
+            } => {
+                let mt = 1.0 - t;
+                (
+                    6.0 * mt * (start.0 - 2.0 * control1.0 + control2.0)
+                        + 6.0 * t * (control1.0 - 2.0 * control2.0 + end.0),
+                    6.0 * mt * (start.1 - 2.0 * control1.1 + control2.1)
+                        + 6.0 * t * (control1.1 - 2.0 * control2.1 + end.1),
+                )
+            }
+            Segment::Arc { .. } => match self.arc_center() {
+                Some(arc) => {
+                    let angle = arc.start_angle + arc.sweep_angle * t;
+                    let (sin_phi, cos_phi) = arc.rotation.sin_cos();
+                    let factor = arc.sweep_angle * arc.sweep_angle;
+                    let dx = -arc.radii.0 * angle.cos() * factor;
+                    let dy = -arc.radii.1 * angle.sin() * factor;
+
+                    (cos_phi * dx - sin_phi * dy, sin_phi * dx + cos_phi * dy)
+                }
+                None => (0.0, 0.0),
+            },
+        }
+    }
+
+    /// Signed curvature at `t`, positive where the curve turns towards positive angles
+    pub fn curvature(&self, t: f64) -> f64 {
+        let (dx, dy) = self.derivative(t);
+        let (ddx, ddy) = self.second_derivative(t);
+        let speed = dx.hypot(dy);
+
+        if speed < 1e-12 {
+            return 0.0;
+        }
+
+        (dx * ddy - dy * ddx) / speed.powi(3)
+    }
+
+    /// Unit tangent at `t`, falling back to the chord for degenerate control points
+    pub fn tangent(&self, t: f64) -> (f64, f64) {
+        let (mut dx, mut dy) = self.derivative(t);
+
+        // a control point on top of an endpoint makes the derivative vanish there
+        if dx.hypot(dy) < 1e-12 {
+            let nudged = if t < 0.5 { t + 1e-6 } else { t - 1e-6 };
+            (dx, dy) = self.derivative(nudged);
+        }
+        if dx.hypot(dy) < 1e-12 {
+            let (start, end) = (self.start(), self.end());
+            (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        }
+
+        let length = dx.hypot(dy);
+        if length == 0.0 {
+            (0.0, 0.0)
+        } else {
+            (dx / length, dy / length)
+        }
+    }
+
+    pub fn length(&self) -> f64 {
+        match *self {
+            Segment::Line { start, end } => distance(start, end),
+            Segment::Quadratic {
+                start,
+                control,
+                end,
+            } => quadratic_bezier_arc_length(start, control, end),
+            Segment::Cubic {
+                start,
+                control1,
+                control2,
+                end,
+            } => cubic_bezier_arc_length(start, control1, control2, end),
+            Segment::Arc { start, end, .. } => match self.arc_center() {
+                Some(arc) => {
+                    // integrate in pieces of at most 45 degrees to keep the quadrature accurate
+                    let pieces = (arc.sweep_angle.abs() / (PI / 4.0)).ceil().max(1.0) as usize;
+
+                    (0..pieces)
+                        .map(|i| {
+                            let t0 = i as f64 / pieces as f64;
+                            let t1 = (i + 1) as f64 / pieces as f64;
+                            self.length_between(t0, t1)
+                        })
+                        .sum()
+                }
+                None => distance(start, end),
+            },
+        }
+    }
+
+    /// Parameter `t` at which the arc length from the start reaches `length`, found with
+    /// Newton steps on the quadrature length, safeguarded by bisection
+    pub fn parameter_at_length(&self, length: f64) -> f64 {
+        let total = self.length();
+
+        if total <= 0.0 || length <= 0.0 {
+            return 0.0;
+        }
+        if length >= total {
+            return 1.0;
+        }
+
+        let (mut low, mut high) = (0.0, 1.0);
+        let mut t = length / total;
+
+        for _ in 0..32 {
+            let error = self.length_between(0.0, t) - length;
+
+            if error.abs() < 1e-9 * total.max(1.0) {
+                break;
+            }
+            if error > 0.0 {
+                high = t;
+            } else {
+                low = t;
+            }
+
+            let (dx, dy) = self.derivative(t);
+            let speed = dx.hypot(dy);
+            let next = t - error / speed;
+
+            t = if speed > 1e-12 && next > low && next < high {
+                next
+            } else {
+                (low + high) / 2.0
+            };
+        }
+
+        t
+    }
+
+    /// Arc length between two parameters using 7-point Gaussian quadrature
+    pub fn length_between(&self, t0: f64, t1: f64) -> f64 {
+        let half = (t1 - t0) / 2.0;
+
+        GAUSS_POINTS
+            .iter()
+            .map(|(x, w)| {
+                let (dx, dy) = self.derivative(t0 + (x + 1.0) * half);
+                (dx * dx + dy * dy).sqrt() * w * half
+            })
+            .sum()
+    }
+
+    /// Points along the segment, no further than `tolerance` from the true curve.
+    /// The start point is included, so consecutive segments share their joints.
+    pub fn flatten(&self, tolerance: f64) -> Vec<(f64, f64)> {
+        let tolerance = tolerance.max(1e-9);
+
+        // Wang's formula: n >= sqrt(d (d - 1) / 8 * max |second difference| / tolerance)
+        let second_difference = |a: (f64, f64), b: (f64, f64), c: (f64, f64)| {
+            (a.0 - 2.0 * b.0 + c.0).hypot(a.1 - 2.0 * b.1 + c.1)
+        };
+
+        let n = match *self {
+            Segment::Line { .. } => 1.0,
+            Segment::Quadratic {
+                start,
+                control,
+                end,
+            } => (0.25 * second_difference(start, control, end) / tolerance).sqrt(),
+            Segment::Cubic {
+                start,
+                control1,
+                control2,
+                end,
+            } => {
+                let m = second_difference(start, control1, control2)
+                    .max(second_difference(control1, control2, end));
+                (0.75 * m / tolerance).sqrt()
+            }
+            Segment::Arc { .. } => match self.arc_center() {
+                Some(arc) => {
+                    let radius = arc.radii.0.max(arc.radii.1);
+                    let step = 2.0 * (1.0 - (tolerance / radius).min(1.0)).acos();
+                    arc.sweep_angle.abs() / step.max(1e-3)
+                }
+                None => 1.0,
+            },
+        };
+
+        let n = (n.ceil() as usize).clamp(1, 10_000);
+
+        (0..=n)
+            .map(|i| self.point_at(i as f64 / n as f64))
+            .collect()
+    }
+
+    /// Parameters in (0, 1) where the x (`axis` 0) or y (`axis` 1) coordinate has a local
+    /// extremum, sorted. Between them the segment is monotone in that coordinate.
+    pub(crate) fn extrema(&self, axis: usize) -> Vec<f64> {
+        let pick = |point: (f64, f64)| if axis == 0 { point.0 } else { point.1 };
+
+        let mut ts = match *self {
+            Segment::Line { .. } => vec![],
+            Segment::Quadratic {
+                start,
+                control,
+                end,
+            } => {
+                let (a, b, c) = (pick(start), pick(control), pick(end));
+                let denominator = a - 2.0 * b + c;
+                if denominator != 0.0 {
+                    vec![(a - b) / denominator]
+                } else {
+                    vec![]
+                }
+            }
+            Segment::Cubic {
+                start,
+                control1,
+                control2,
+                end,
+            } => {
+                let (p0, p1, p2, p3) = (pick(start), pick(control1), pick(control2), pick(end));
+
+                // derivative / 3 = a t^2 + b t + c
+                let a = -p0 + 3.0 * p1 - 3.0 * p2 + p3;
+                let b = 2.0 * (p0 - 2.0 * p1 + p2);
+                let c = p1 - p0;
+                solve_quadratic(a, b, c)
+            }
+            Segment::Arc { .. } => match self.arc_center() {
+                Some(arc) => {
+                    let (sin_phi, cos_phi) = arc.rotation.sin_cos();
+                    let base = if axis == 0 {
+                        (-arc.radii.1 * sin_phi).atan2(arc.radii.0 * cos_phi)
+                    } else {
+                        (arc.radii.1 * cos_phi).atan2(arc.radii.0 * sin_phi)
+                    };
+
+                    (-3..=3)
+                        .map(|k| (base + k as f64 * PI - arc.start_angle) / arc.sweep_angle)
+                        .collect()
+                }
+                None => vec![],
+            },
+        };
+
+        ts.retain(|t| *t > 0.0 && *t < 1.0);
+        ts.sort_by(f64::total_cmp);
+        ts
+    }
+
+    /// Tight axis-aligned bounding box as (min, max)
+    pub fn bounding_box(&self) -> ((f64, f64), (f64, f64)) {
+        let mut extrema = self.extrema(0);
+        extrema.extend(self.extrema(1));
+
+        self.bounding_box_between(0.0, 1.0, &extrema)
+    }
+
+    /// Tight bounding box of the part between `t0` and `t1`, given the parameters of all
+    /// coordinate extrema from `extrema`
+    pub(crate) fn bounding_box_between(
+        &self,
+        t0: f64,
+        t1: f64,
+        extrema: &[f64],
+    ) -> ((f64, f64), (f64, f64)) {
+        let mut min = (f64::INFINITY, f64::INFINITY);
+        let mut max = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+
+        // the extremes are at the ends or where a coordinate's derivative vanishes
+        let ts = [t0, t1]
+            .into_iter()
+            .chain(extrema.iter().copied().filter(|t| *t > t0 && *t < t1));
+
+        for t in ts {
+            let (x, y) = self.point_at(t);
+            min = (min.0.min(x), min.1.min(y));
+            max = (max.0.max(x), max.1.max(y));
+        }
+
+        (min, max)
+    }
+
+    /// The part of the segment between `t0` and `t1`, as a segment of the same kind.
+    /// Béziers use the blossom of their control points, arcs keep their ellipse.
+    pub(crate) fn subsegment(&self, t0: f64, t1: f64) -> Segment {
+        let (start, end) = (self.point_at(t0), self.point_at(t1));
+
+        match *self {
+            Segment::Line { .. } => Segment::Line { start, end },
+            Segment::Quadratic {
+                start: p0,
+                control: p1,
+                end: p2,
+            } => Segment::Quadratic {
+                start,
+                control: lerp(lerp(p0, p1, t0), lerp(p1, p2, t0), t1),
+                end,
+            },
+            Segment::Cubic {
+                start: p0,
+                control1: p1,
+                control2: p2,
+                end: p3,
+            } => {
+                // blossom b(u, v, w): one de Casteljau step per argument
+                let blossom = |u: f64, v: f64, w: f64| {
+                    let (a, b, c) = (lerp(p0, p1, u), lerp(p1, p2, u), lerp(p2, p3, u));
+                    let (d, e) = (lerp(a, b, v), lerp(b, c, v));
+                    lerp(d, e, w)
+                };
+
+                Segment::Cubic {
+                    start,
+                    control1: blossom(t0, t0, t1),
+                    control2: blossom(t0, t1, t1),
+                    end,
+                }
+            }
+            Segment::Arc {
+                rotation, sweep, ..
+            } => match self.arc_center() {
+                // the radii as scaled to reach the original end point, so the ellipse stays put
+                Some(arc) => Segment::Arc {
+                    start,
+                    radii: arc.radii,
+                    rotation,
+                    large_arc: (arc.sweep_angle * (t1 - t0)).abs() > PI,
+                    sweep,
+                    end,
+                },
+                None => Segment::Line { start, end },
+            },
+        }
+    }
+
+    /// The segment with its endpoints moved, control points and arc parameters unchanged
+    pub(crate) fn with_endpoints(&self, start: (f64, f64), end: (f64, f64)) -> Segment {
+        match *self {
+            Segment::Line { .. } => Segment::Line { start, end },
+            Segment::Quadratic { control, .. } => Segment::Quadratic {
+                start,
+                control,
+                end,
+            },
+            Segment::Cubic {
+                control1, control2, ..
+            } => Segment::Cubic {
+                start,
+                control1,
+                control2,
+                end,
+            },
+            Segment::Arc {
+                radii,
+                rotation,
+                large_arc,
+                sweep,
+                ..
+            } => Segment::Arc {
+                start,
+                radii,
+                rotation,
+                large_arc,
+                sweep,
+                end,
+            },
+        }
+    }
+
+    /// The same curve traversed from end to start
+    pub fn reversed(&self) -> Segment {
+        match *self {
+            Segment::Line { start, end } => Segment::Line {
+                start: end,
+                end: start,
+            },
+            Segment::Quadratic {
+                start,
+                control,
+                end,
+            } => Segment::Quadratic {
+                start: end,
+                control,
+                end: start,
+            },
+            Segment::Cubic {
+                start,
+                control1,
+                control2,
+                end,
+            } => Segment::Cubic {
+                start: end,
+                control1: control2,
+                control2: control1,
+                end: start,
+            },
+            Segment::Arc {
+                start,
+                radii,
+                rotation,
+                large_arc,
+                sweep,
+                end,
+            } => Segment::Arc {
+                start: end,
+                radii,
+                rotation,
+                large_arc,
+                sweep: !sweep,
+                end: start,
+            },
+        }
+    }
+
+    /// Converts the SVG endpoint parameterization of an arc into its center form.
+    /// Returns `None` for anything that is not an arc or for arcs that degrade to lines.
+    pub(crate) fn arc_center(&self) -> Option<ArcCenter> {
+        let Segment::Arc {
+            start,
+            radii,
+            rotation,
+            large_arc,
+            sweep,
+            end,
+        } = *self
+        else {
+            return None;
+        };
+
+        let (mut rx, mut ry) = (radii.0.abs(), radii.1.abs());
+
+        if rx == 0.0 || ry == 0.0 || start == end {
+            return None;
+        }
+
+        // SVG implementation notes, F.6.5
+        let phi = rotation.to_radians();
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        let dx = (start.0 - end.0) / 2.0;
+        let dy = (start.1 - end.1) / 2.0;
+        let x1 = cos_phi * dx + sin_phi * dy;
+        let y1 = -sin_phi * dx + cos_phi * dy;
+
+        // scale up radii that are too small to reach the end point
+        let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+        if lambda > 1.0 {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+
+        let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+        let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+        let mut coefficient = (numerator / denominator).max(0.0).sqrt();
+        if large_arc == sweep {
+            coefficient = -coefficient;
+        }
+
+        let cx1 = coefficient * rx * y1 / ry;
+        let cy1 = -coefficient * ry * x1 / rx;
+
+        let center = (
+            cos_phi * cx1 - sin_phi * cy1 + (start.0 + end.0) / 2.0,
+            sin_phi * cx1 + cos_phi * cy1 + (start.1 + end.1) / 2.0,
+        );
+
+        let start_angle = ((y1 - cy1) / ry).atan2((x1 - cx1) / rx);
+        let end_angle = ((-y1 - cy1) / ry).atan2((-x1 - cx1) / rx);
+        let mut sweep_angle = end_angle - start_angle;
+
+        if sweep && sweep_angle < 0.0 {
+            sweep_angle += 2.0 * PI;
+        } else if !sweep && sweep_angle > 0.0 {
+            sweep_angle -= 2.0 * PI;
+        }
+
+        Some(ArcCenter {
+            center,
+            radii: (rx, ry),
+            rotation: phi,
+            start_angle,
+            sweep_angle,
+        })
+    }
+}
+
+pub(crate) fn lerp(a: (f64, f64), b: (f64, f64), t: f64) -> (f64, f64) {
+    (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+}
+
+pub(crate) fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (b.0 - a.0).hypot(b.1 - a.1)
+}
+
+/// Distance from `p` to the line segment `a`-`b`
+pub(crate) fn distance_to_line_segment(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length_squared = dx * dx + dy * dy;
+
+    if length_squared == 0.0 {
+        return distance(p, a);
+    }
+
+    let t = (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / length_squared).clamp(0.0, 1.0);
+
+    distance(p, lerp(a, b, t))
+}
+
+/// Real roots of a t^2 + b t + c, degrading to the linear case when `a` vanishes
+pub(crate) fn solve_quadratic(a: f64, b: f64, c: f64) -> Vec<f64> {
+    if a.abs() < 1e-12 {
+        if b.abs() < 1e-12 {
+            return vec![];
+        }
+        return vec![-c / b];
+    }
+
+    let discriminant = b * b - 4.0 * a * c;
+
+    if discriminant < 0.0 {
+        vec![]
+    } else {
+        let root = discriminant.sqrt();
+        vec![(-b + root) / (2.0 * a), (-b - root) / (2.0 * a)]
+    }
+}
+
+/// Bounding box of several boxes, `None` when there are none
+pub(crate) fn union_boxes(
+    boxes: impl IntoIterator<Item = ((f64, f64), (f64, f64))>,
+) -> Option<((f64, f64), (f64, f64))> {
+    boxes.into_iter().reduce(|(min_a, max_a), (min_b, max_b)| {
+        (
+            (min_a.0.min(min_b.0), min_a.1.min(min_b.1)),
+            (max_a.0.max(max_b.0), max_a.1.max(max_b.1)),
+        )
+    })
+}
+
+/// Number of arguments each path command takes
+fn command_arity(command: char) -> usize {
+    match command.to_ascii_uppercase() {
+        'M' | 'L' | 'T' => 2,
+        'H' | 'V' => 1,
+        'S' | 'Q' => 4,
+        'C' => 6,
+        'A' => 7,
+        _ => 0,
+    }
+}
+
+/// Resolves path tokens into absolute segments grouped by subpath. Handles every SVG path
+/// command, relative variants, implicit command repetition and S/T control point reflection.
+pub(crate) fn parse_subpaths(commands: &[String]) -> Vec<Subpath> {
+    let mut subpaths: Vec<Subpath> = vec![];
+    let mut current = (0.0, 0.0);
+
+    // control point of the previous C/S or Q/T, used for reflection
+    let mut last_cubic_control: Option<(f64, f64)> = None;
+    let mut last_quadratic_control: Option<(f64, f64)> = None;
+
+    let mut pointer = 0;
+
+    while pointer < commands.len() {
+        let command = commands[pointer]
+            .chars()
+            .next()
+            .expect("empty token in path");
+        pointer += 1;
+
+        if !command.is_alphabetic() {
+            continue;
+        }
+
+        let arity = command_arity(command);
+        let relative = command.is_ascii_lowercase();
+        let mut first = true;
+
+        loop {
+            if matches!(command, 'Z' | 'z') {
+                if let Some(subpath) = subpaths.last_mut() {
+                    if current != subpath.start {
+                        subpath.segments.push(Segment::Line {
+                            start: current,
+                            end: subpath.start,
+                        });
+                    }
+                    subpath.closed = true;
+                    current = subpath.start;
+                }
+                last_cubic_control = None;
+                last_quadratic_control = None;
+                break;
+            }
+
+            let available = commands[pointer..]
+                .iter()
+                .take_while(|token| !token.starts_with(|c: char| c.is_alphabetic()))
+                .count();
+
+            if arity == 0 || available < arity {
+                break;
+            }
+
+            let args: Vec<f64> = commands[pointer..pointer + arity]
+                .iter()
+                .map(|token| token.parse::<f64>().expect("not valid nr"))
+                .collect();
+            pointer += arity;
+
+            let origin = if relative { current } else { (0.0, 0.0) };
+            let at = |i: usize| (args[i] + origin.0, args[i + 1] + origin.1);
+
+            let mut cubic_control = None;
+            let mut quadratic_control = None;
+
+            let segment = match command.to_ascii_uppercase() {
+                'M' if first => {
+                    current = at(0);
+                    subpaths.push(Subpath {
+                        start: current,
+                        segments: vec![],
+                        closed: false,
+                    });
+                    None
+                }
+                // extra coordinate pairs after a moveto are implicit linetos
+                'M' | 'L' => Some(Segment::Line {
+                    start: current,
+                    end: at(0),
+                }),
+                'H' => Some(Segment::Line {
+                    start: current,
+                    end: (args[0] + origin.0, current.1),
+                }),
+                'V' => Some(Segment::Line {
+                    start: current,
+                    end: (current.0, args[0] + origin.1),
+                }),
+                'C' => {
+                    cubic_control = Some(at(2));
+                    Some(Segment::Cubic {
+                        start: current,
+                        control1: at(0),
+                        control2: at(2),
+                        end: at(4),
+                    })
+                }
+                'S' => {
+                    cubic_control = Some(at(0));
+                    Some(Segment::Cubic {
+                        start: current,
+                        control1: reflect(last_cubic_control, current),
+                        control2: at(0),
+                        end: at(2),
+                    })
+                }
+                'Q' => {
+                    quadratic_control = Some(at(0));
+                    Some(Segment::Quadratic {
+                        start: current,
+                        control: at(0),
+                        end: at(2),
+                    })
+                }
+                'T' => {
+                    let control = reflect(last_quadratic_control, current);
+                    quadratic_control = Some(control);
+                    Some(Segment::Quadratic {
+                        start: current,
+                        control,
+                        end: at(0),
+                    })
+                }
+                'A' => Some(Segment::Arc {
+                    start: current,
+                    radii: (args[0], args[1]),
+                    rotation: args[2],
+                    large_arc: args[3] != 0.0,
+                    sweep: args[4] != 0.0,
+                    end: at(5),
+                }),
+                _ => None,
+            };
+
+            if let Some(segment) = segment {
+                // drawing without a preceding moveto starts a subpath at the current point
+                if subpaths.is_empty() || subpaths.last().is_some_and(|s| s.closed) {
+                    subpaths.push(Subpath {
+                        start: current,
+                        segments: vec![],
+                        closed: false,
+                    });
+                }
+
+                current = segment.end();
+                subpaths
+                    .last_mut()
+                    .expect("subpath exists")
+                    .segments
+                    .push(segment);
+            }
+
+            last_cubic_control = cubic_control;
+            last_quadratic_control = quadratic_control;
+            first = false;
+        }
+    }
+
+    subpaths
+}
+
+/// Reflects the previous control point about the current point, or returns the current point
+fn reflect(control: Option<(f64, f64)>, current: (f64, f64)) -> (f64, f64) {
+    match control {
+        Some(control) => (2.0 * current.0 - control.0, 2.0 * current.1 - control.1),
+        None => current,
+    }
+}
+
+impl Path {
+    /// Parses the path into typed, absolute segments grouped by subpath
+    pub fn subpaths(&self) -> Vec<Subpath> {
+        parse_subpaths(&self.commands)
+    }
+
+    /// All segments of the path in drawing order, ignoring subpath boundaries
+    pub fn segments(&self) -> Vec<Segment> {
+        self.subpaths()
+            .into_iter()
+            .flat_map(|subpath| subpath.segments)
+            .collect()
+    }
+
+    /// Tight bounding box of the path as (min, max), `None` for an empty path
+    pub fn bounding_box(&self) -> Option<((f64, f64), (f64, f64))> {
+        union_boxes(self.subpaths().iter().filter_map(Subpath::bounding_box))
+    }
+}
+
+impl Subpath {
+    /// Tight bounding box of the subpath as (min, max), including its start point
+    pub fn bounding_box(&self) -> Option<((f64, f64), (f64, f64))> {
+        union_boxes(
+            std::iter::once((self.start, self.start))
+                .chain(self.segments.iter().map(Segment::bounding_box)),
+        )
+    }
+
+    /// Flattened outline of the subpath, see `Segment::flatten`
+    pub fn flatten(&self, tolerance: f64) -> Vec<(f64, f64)> {
+        let mut points = vec![self.start];
+
+        for segment in &self.segments {
+            points.extend(segment.flatten(tolerance).into_iter().skip(1));
+        }
+
+        points
+    }
+}
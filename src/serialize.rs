@@ -0,0 +1,83 @@
+use crate::{Path, Segment, Subpath};
+
+/// Writes subpaths as plain SVG path data with absolute commands, e.g. "M 0 0 L 10 0 Z"
+pub fn write_path_data(subpaths: &[Subpath]) -> String {
+    let mut tokens: Vec<String> = vec![];
+
+    for subpath in subpaths {
+        tokens.push("M".to_string());
+        tokens.push(subpath.start.0.to_string());
+        tokens.push(subpath.start.1.to_string());
+
+        let mut segments = subpath.segments.as_slice();
+
+        // the closing line is implied by Z
+        if subpath.closed {
+            if let Some((Segment::Line { end, .. }, rest)) = segments.split_last() {
+                if *end == subpath.start {
+                    segments = rest;
+                }
+            }
+        }
+
+        for segment in segments {
+            let (command, values) = segment_values(segment);
+            tokens.push(command.to_string());
+            tokens.extend(values.iter().map(|value| value.to_string()));
+        }
+
+        if subpath.closed {
+            tokens.push("Z".to_string());
+        }
+    }
+
+    tokens.join(" ")
+}
+
+/// Absolute command letter and arguments of a segment, without its start point
+pub(crate) fn segment_values(segment: &Segment) -> (char, Vec<f64>) {
+    match *segment {
+        Segment::Line { end, .. } => ('L', vec![end.0, end.1]),
+        Segment::Quadratic { control, end, .. } => ('Q', vec![control.0, control.1, end.0, end.1]),
+        Segment::Cubic {
+            control1,
+            control2,
+            end,
+            ..
+        } => (
+            'C',
+            vec![control1.0, control1.1, control2.0, control2.1, end.0, end.1],
+        ),
+        Segment::Arc {
+            radii,
+            rotation,
+            large_arc,
+            sweep,
+            end,
+            ..
+        } => (
+            'A',
+            vec![
+                radii.0,
+                radii.1,
+                rotation,
+                large_arc as u8 as f64,
+                sweep as u8 as f64,
+                end.0,
+                end.1,
+            ],
+        ),
+    }
+}
+
+impl Path {
+    /// Builds a path from already resolved subpaths
+    pub fn from_subpaths(subpaths: &[Subpath]) -> Self {
+        Path::init(&write_path_data(subpaths))
+    }
+
+    /// Serializes the path back into SVG path data
+    pub fn to_path_data(&self) -> String {
+        write_path_data(&self.subpaths())
+    }
+}
@@ -0,0 +1,306 @@
+use crate::segment::distance_to_line_segment;
+use crate::{Path, Sample};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Cosine of the smallest turn at a joint between segments that counts as a corner, about 1°
+const CORNER_COSINE: f64 = 0.9998;
+
+/// How a polyline is thinned out
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Simplification {
+    /// Ramer–Douglas–Peucker: no dropped point is further than `tolerance` from the result
+    DouglasPeucker { tolerance: f64 },
+    /// Visvalingam–Whyatt: drop points while their effective triangle area is below `area`
+    VisvalingamArea { area: f64 },
+    /// Visvalingam–Whyatt: drop the least significant points until `count` are left
+    VisvalingamCount { count: usize },
+}
+
+/// Triangle area spanned by a point and its two neighbours, as a heap entry ordered so the
+/// smallest area comes out first
+#[derive(PartialEq)]
+struct Candidate {
+    area: f64,
+    index: usize,
+}
+
+impl Eq for Candidate {}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .area
+            .total_cmp(&self.area)
+            .then(other.index.cmp(&self.index))
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn triangle_area(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> f64 {
+    ((b.0 - a.0) * (c.1 - a.1) - (c.0 - a.0) * (b.1 - a.1)).abs() / 2.0
+}
+
+/// Marks the points RDP keeps between `first` and `last`, which are kept already
+fn douglas_peucker(
+    points: &[(f64, f64)],
+    (first, last): (usize, usize),
+    tolerance: f64,
+    kept: &mut [bool],
+) {
+    let mut stack = vec![(first, last)];
+
+    while let Some((start, end)) = stack.pop() {
+        let farthest = (start + 1..end)
+            .map(|i| {
+                (
+                    i,
+                    distance_to_line_segment(points[i], points[start], points[end]),
+                )
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+
+        if let Some((index, distance)) = farthest {
+            if distance > tolerance {
+                kept[index] = true;
+                stack.push((start, index));
+                stack.push((index, end));
+            }
+        }
+    }
+}
+
+/// Removes the points with the smallest effective area, never crossing from one polyline to
+/// the next, until `done(area, remaining)` says to stop. The effective area of a point never
+/// drops below that of a point removed before it, so removals happen in order of significance.
+fn visvalingam(
+    points: &[(f64, f64)],
+    polylines: &[(usize, usize)],
+    kept: &mut [bool],
+    fixed: &[bool],
+    done: impl Fn(f64, usize) -> bool,
+) {
+    let mut previous: Vec<Option<usize>> = vec![None; points.len()];
+    let mut next: Vec<Option<usize>> = vec![None; points.len()];
+    let mut areas = vec![f64::INFINITY; points.len()];
+    let mut heap = BinaryHeap::new();
+
+    for &(first, last) in polylines {
+        for i in first..=last {
+            previous[i] = (i > first).then(|| i - 1);
+            next[i] = (i < last).then_some(i + 1);
+
+            if let (Some(p), Some(n)) = (previous[i], next[i]) {
+                if !fixed[i] {
+                    areas[i] = triangle_area(points[p], points[i], points[n]);
+                    heap.push(Candidate {
+                        area: areas[i],
+                        index: i,
+                    });
+                }
+            }
+        }
+    }
+
+    let mut remaining = kept.iter().filter(|kept| **kept).count();
+    let mut largest_removed: f64 = 0.0;
+
+    while let Some(Candidate { area, index }) = heap.pop() {
+        // entries from before a neighbour was removed are stale
+        if !kept[index] || area != areas[index] {
+            continue;
+        }
+        if done(area, remaining) {
+            break;
+        }
+
+        kept[index] = false;
+        remaining -= 1;
+        largest_removed = largest_removed.max(area);
+
+        let (p, n) = (previous[index], next[index]);
+        if let Some(p) = p {
+            next[p] = n;
+        }
+        if let Some(n) = n {
+            previous[n] = p;
+        }
+
+        for neighbour in [p, n].into_iter().flatten() {
+            if let (Some(before), Some(after)) = (previous[neighbour], next[neighbour]) {
+                if !fixed[neighbour] {
+                    let triangle = triangle_area(points[before], points[neighbour], points[after]);
+                    areas[neighbour] = triangle.max(largest_removed);
+                    heap.push(Candidate {
+                        area: areas[neighbour],
+                        index: neighbour,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Which of the points of several polylines, given as inclusive index ranges, survive
+/// `method`. The ends of every polyline and the points flagged in `fixed` always do.
+fn simplify(
+    points: &[(f64, f64)],
+    polylines: &[(usize, usize)],
+    fixed: &[bool],
+    method: Simplification,
+) -> Vec<bool> {
+    let mut fixed = fixed.to_vec();
+    fixed.resize(points.len(), false);
+    for &(first, last) in polylines {
+        fixed[first] = true;
+        fixed[last] = true;
+    }
+
+    let mut kept = vec![true; points.len()];
+
+    match method {
+        Simplification::DouglasPeucker { tolerance } => {
+            kept.clone_from(&fixed);
+
+            // fixed points split the polylines into runs that are simplified on their own
+            for &(first, last) in polylines {
+                let anchors: Vec<usize> = (first..=last).filter(|i| fixed[*i]).collect();
+                for pair in anchors.windows(2) {
+                    douglas_peucker(points, (pair[0], pair[1]), tolerance, &mut kept);
+                }
+            }
+        }
+        Simplification::VisvalingamArea { area } => {
+            visvalingam(points, polylines, &mut kept, &fixed, |smallest, _| {
+                smallest >= area
+            });
+        }
+        Simplification::VisvalingamCount { count } => {
+            visvalingam(points, polylines, &mut kept, &fixed, |_, remaining| {
+                remaining <= count
+            });
+        }
+    }
+
+    kept
+}
+
+/// Indices of the points of one polyline that survive `method`, in order. The first and last
+/// points are always kept, as are those flagged in `corners`.
+pub fn simplify_polyline(
+    points: &[(f64, f64)],
+    corners: &[bool],
+    method: Simplification,
+) -> Vec<usize> {
+    if points.is_empty() {
+        return vec![];
+    }
+
+    simplify(points, &[(0, points.len() - 1)], corners, method)
+        .iter()
+        .enumerate()
+        .filter(|(_, kept)| **kept)
+        .map(|(index, _)| index)
+        .collect()
+}
+
+impl Path {
+    /// `samples` thinned out with `method`. The first and last sample of every subpath are
+    /// kept, and so are joints where the path turns a corner, so sharp features survive any
+    /// threshold.
+    pub fn simplified_samples(&self, method: Simplification) -> Vec<Sample> {
+        let samples = self.samples();
+        let subpaths = self.subpaths();
+        let points: Vec<(f64, f64)> = samples.iter().map(|sample| sample.point).collect();
+
+        let mut polylines = vec![];
+        let mut first = 0;
+        for i in 1..=samples.len() {
+            if i == samples.len() || samples[i].subpath != samples[first].subpath {
+                polylines.push((first, i - 1));
+                first = i;
+            }
+        }
+
+        // a joint appears once, as the end of the segment before it
+        let corners: Vec<bool> = samples
+            .iter()
+            .enumerate()
+            .map(|(i, sample)| {
+                let Some(following) = samples.get(i + 1) else {
+                    return false;
+                };
+                if following.subpath != sample.subpath || following.segment == sample.segment {
+                    return false;
+                }
+
+                let segments = &subpaths[sample.subpath].segments;
+                let (incoming, outgoing) = (
+                    segments[sample.segment].tangent(1.0),
+                    segments[following.segment].tangent(0.0),
+                );
+                incoming.0 * outgoing.0 + incoming.1 * outgoing.1 < CORNER_COSINE
+            })
+            .collect();
+
+        simplify(&points, &polylines, &corners, method)
+            .into_iter()
+            .zip(samples)
+            .filter(|(kept, _)| *kept)
+            .map(|(_, sample)| sample)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A slightly bent line to a right-angled corner and up from it
+    const POINTS: [(f64, f64); 6] = [
+        (0.0, 0.0),
+        (1.0, 0.01),
+        (2.0, 0.0),
+        (3.0, 0.0),
+        (3.0, 1.0),
+        (3.0, 2.0),
+    ];
+
+    #[test]
+    fn douglas_peucker_keeps_ends_and_the_corner() {
+        let method = Simplification::DouglasPeucker { tolerance: 0.1 };
+        assert_eq!(simplify_polyline(&POINTS, &[false; 6], method), [0, 3, 5]);
+
+        let mut corners = [false; 6];
+        corners[1] = true;
+        assert_eq!(simplify_polyline(&POINTS, &corners, method), [0, 1, 3, 5]);
+    }
+
+    #[test]
+    fn visvalingam_keeps_ends_and_flagged_points() {
+        let by_area = Simplification::VisvalingamArea { area: 0.1 };
+        assert_eq!(simplify_polyline(&POINTS, &[false; 6], by_area), [0, 3, 5]);
+
+        let by_count = Simplification::VisvalingamCount { count: 2 };
+        assert_eq!(simplify_polyline(&POINTS, &[false; 6], by_count), [0, 5]);
+
+        let mut corners = [false; 6];
+        corners[4] = true;
+        assert_eq!(simplify_polyline(&POINTS, &corners, by_count), [0, 4, 5]);
+    }
+
+    #[test]
+    fn keeps_the_joints_of_sampled_corners() {
+        let samples = Path::init("M0 0 H10 V10")
+            .simplified_samples(Simplification::DouglasPeucker { tolerance: 1.0 });
+        let points: Vec<_> = samples.iter().map(|sample| sample.point).collect();
+        assert_eq!(points.len(), 3);
+        assert_eq!(points[1], (10.0, 0.0));
+    }
+}
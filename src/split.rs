@@ -0,0 +1,148 @@
+use crate::sample::LengthTable;
+use crate::segment::lerp;
+use crate::{Path, Segment};
+
+impl Segment {
+    /// Splits the segment at `t` into the parts before and after it, which share the point
+    /// at `t` exactly. Béziers are split with de Casteljau's algorithm, arcs by angle so both
+    /// parts stay on the same ellipse.
+    pub fn split_at(&self, t: f64) -> (Segment, Segment) {
+        let t = t.clamp(0.0, 1.0);
+
+        match *self {
+            Segment::Line { start, end } => {
+                let middle = lerp(start, end, t);
+                (
+                    Segment::Line { start, end: middle },
+                    Segment::Line { start: middle, end },
+                )
+            }
+            Segment::Quadratic {
+                start,
+                control,
+                end,
+            } => {
+                let (a, b) = (lerp(start, control, t), lerp(control, end, t));
+                let middle = lerp(a, b, t);
+                (
+                    Segment::Quadratic {
+                        start,
+                        control: a,
+                        end: middle,
+                    },
+                    Segment::Quadratic {
+                        start: middle,
+                        control: b,
+                        end,
+                    },
+                )
+            }
+            Segment::Cubic {
+                start,
+                control1,
+                control2,
+                end,
+            } => {
+                let (a, b, c) = (
+                    lerp(start, control1, t),
+                    lerp(control1, control2, t),
+                    lerp(control2, end, t),
+                );
+                let (d, e) = (lerp(a, b, t), lerp(b, c, t));
+                let middle = lerp(d, e, t);
+                (
+                    Segment::Cubic {
+                        start,
+                        control1: a,
+                        control2: d,
+                        end: middle,
+                    },
+                    Segment::Cubic {
+                        start: middle,
+                        control1: e,
+                        control2: c,
+                        end,
+                    },
+                )
+            }
+            Segment::Arc { .. } => (self.subsegment(0.0, t), self.subsegment(t, 1.0)),
+        }
+    }
+
+    /// The part of the segment between the parameters `t0` and `t1`, drawn from `t0` to `t1`
+    /// and so backwards when `t0 > t1`
+    pub fn slice(&self, t0: f64, t1: f64) -> Segment {
+        let (t0, t1) = (t0.clamp(0.0, 1.0), t1.clamp(0.0, 1.0));
+        if t0 > t1 {
+            return self.subsegment(t1, t0).reversed();
+        }
+        self.subsegment(t0, t1)
+    }
+}
+
+impl Path {
+    /// The exact part of the path between two arc-length positions from its start. A range
+    /// that spans several subpaths keeps them apart; curves stay curves.
+    pub fn slice(&self, start_length: f64, end_length: f64) -> Path {
+        let table = LengthTable::new(&self.subpaths());
+        Path::from_subpaths(&table.slice(start_length, end_length))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::segment::distance;
+
+    fn assert_near(actual: (f64, f64), expected: (f64, f64)) {
+        assert!(
+            distance(actual, expected) < 1e-9,
+            "{:?} instead of {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn split_parts_meet_and_add_up() {
+        for data in [
+            "M0 0 L10 5",
+            "M0 0 Q5 4 10 0",
+            "M0 0 C0 10 10 10 10 0",
+            "M0 0 A10 5 30 0 1 10 0",
+        ] {
+            let segment = Path::init(data).segments()[0];
+            let (before, after) = segment.split_at(0.3);
+
+            assert_eq!(before.start(), segment.start());
+            assert_eq!(before.end(), after.start());
+            assert_eq!(after.end(), segment.end());
+            assert_near(before.end(), segment.point_at(0.3));
+            // the lengths are 7-point quadrature sums, which are off by about 1e-7 here
+            assert!((before.length() + after.length() - segment.length()).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn backwards_slices_are_reversed() {
+        let half_circle = Path::init("M-5 0 A5 5 0 0 1 5 0").segments()[0];
+        let slice = half_circle.slice(0.75, 0.25);
+
+        assert_near(slice.start(), half_circle.point_at(0.75));
+        assert_near(slice.end(), half_circle.point_at(0.25));
+        // on the same half of the circle, not the mirrored arc
+        assert_near(slice.point_at(0.5), half_circle.point_at(0.5));
+    }
+
+    #[test]
+    fn path_slices_follow_arc_length() {
+        let slice = Path::init("M0 0 H10 V10").slice(3.0, 12.0);
+        let segments = slice.segments();
+
+        assert_eq!(segments.len(), 2);
+        assert_near(segments[0].start(), (3.0, 0.0));
+        assert_near(segments[1].end(), (10.0, 2.0));
+        let length: f64 = segments.iter().map(Segment::length).sum();
+        assert!((length - 9.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,237 @@
+use crate::offset::offset_subpath;
+use crate::{BooleanOp, FillRule, LineJoin, OffsetOptions, Path, Segment, Subpath};
+
+/// Shape of the ends of open subpaths, as in SVG's `stroke-linecap`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LineCap {
+    Butt,
+    Round,
+    Square,
+}
+
+/// Stroke properties, defaulting to SVG's initial values
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StrokeStyle {
+    pub width: f64,
+    pub join: LineJoin,
+    pub cap: LineCap,
+    pub miter_limit: f64,
+    /// largest distance allowed between the outline and the true edge of the stroke
+    pub tolerance: f64,
+}
+
+impl Default for StrokeStyle {
+    fn default() -> Self {
+        StrokeStyle {
+            width: 1.0,
+            join: LineJoin::Miter,
+            cap: LineCap::Butt,
+            miter_limit: 4.0,
+            tolerance: 0.01,
+        }
+    }
+}
+
+/// Segments closing the stroke at an end, from the edge on one side to the edge on the
+/// other. `direction` is the unit vector pointing away from the path.
+fn cap(
+    style: &StrokeStyle,
+    from: (f64, f64),
+    to: (f64, f64),
+    direction: (f64, f64),
+) -> Vec<Segment> {
+    let half = style.width / 2.0;
+    let line = |start, end| Segment::Line { start, end };
+
+    match style.cap {
+        LineCap::Butt => vec![line(from, to)],
+        LineCap::Square => {
+            let (dx, dy) = (direction.0 * half, direction.1 * half);
+            let (from_out, to_out) = ((from.0 + dx, from.1 + dy), (to.0 + dx, to.1 + dy));
+            vec![
+                line(from, from_out),
+                line(from_out, to_out),
+                line(to_out, to),
+            ]
+        }
+        // half a turn from the left edge to the right edge, both seen along `direction`
+        LineCap::Round => vec![Segment::Arc {
+            start: from,
+            radii: (half, half),
+            rotation: 0.0,
+            large_arc: false,
+            sweep: true,
+            end: to,
+        }],
+    }
+}
+
+/// Round or square dot drawn for a subpath without length, `None` for butt caps
+fn dot(style: &StrokeStyle, center: (f64, f64)) -> Option<Subpath> {
+    let half = style.width / 2.0;
+    let left = (center.0, center.1 - half);
+    let right = (center.0, center.1 + half);
+
+    let mut segments = cap(style, left, right, (1.0, 0.0));
+    segments.extend(cap(style, right, left, (-1.0, 0.0)));
+
+    (style.cap != LineCap::Butt).then_some(Subpath {
+        start: left,
+        segments,
+        closed: true,
+    })
+}
+
+/// Outline of the stroke of one subpath, as one or two closed subpaths
+fn stroke_subpath(subpath: &Subpath, style: &StrokeStyle) -> Vec<Subpath> {
+    let half = style.width / 2.0;
+    let options = OffsetOptions {
+        join: style.join,
+        miter_limit: style.miter_limit,
+        tolerance: style.tolerance,
+    };
+
+    let drawn: Vec<&Segment> = subpath
+        .segments
+        .iter()
+        .filter(|segment| segment.length() > 0.0)
+        .collect();
+
+    let (Some(first), Some(last)) = (drawn.first(), drawn.last()) else {
+        // SVG still paints caps on subpaths without length, unless they are a lone moveto
+        if subpath.segments.is_empty() && !subpath.closed {
+            return vec![];
+        }
+        return dot(style, subpath.start).into_iter().collect();
+    };
+
+    let left = offset_subpath(subpath, half, &options);
+    let right = offset_subpath(subpath, -half, &options).map(|right| right.reversed());
+
+    // closed subpaths have an outer and an inner edge, running opposite ways
+    if subpath.closed {
+        return left.into_iter().chain(right).collect();
+    }
+
+    let (Some(left), Some(right)) = (left, right) else {
+        return vec![];
+    };
+
+    let left_end = left.segments.last().map_or(left.start, Segment::end);
+    let right_end = right.segments.last().map_or(right.start, Segment::end);
+    let (start_tangent, end_tangent) = (first.tangent(0.0), last.tangent(1.0));
+
+    let mut segments = left.segments;
+    segments.extend(cap(style, left_end, right.start, end_tangent));
+    segments.extend(right.segments);
+    segments.extend(cap(
+        style,
+        right_end,
+        left.start,
+        (-start_tangent.0, -start_tangent.1),
+    ));
+
+    vec![Subpath {
+        start: left.start,
+        segments,
+        closed: true,
+    }]
+}
+
+impl Path {
+    /// Closed outline of the area covered by stroking the path with `style`. The edges of
+    /// every subpath are offset by half the width, joined and capped, and the pieces are
+    /// merged so overlapping parts of the stroke leave no inner contours behind.
+    pub fn stroke_outline(&self, style: &StrokeStyle) -> Path {
+        let outlines: Vec<Subpath> = self
+            .subpaths()
+            .iter()
+            .flat_map(|subpath| stroke_subpath(subpath, style))
+            .collect();
+
+        Path::from_subpaths(&outlines).boolean(&Path::init(""), BooleanOp::Union, FillRule::NonZero)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn area(data: &str, style: StrokeStyle) -> f64 {
+        let outline = Path::init(data).stroke_outline(&style);
+        if outline.subpaths().is_empty() {
+            0.0
+        } else {
+            outline.signed_area()
+        }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "{} instead of {}",
+            actual,
+            expected
+        );
+    }
+
+    fn style(cap: LineCap) -> StrokeStyle {
+        StrokeStyle {
+            width: 2.0,
+            cap,
+            ..StrokeStyle::default()
+        }
+    }
+
+    #[test]
+    fn caps_add_to_width_times_length() {
+        assert_close(area("M0 0 H10", style(LineCap::Butt)), 20.0);
+        // half the width past each end
+        assert_close(area("M0 0 H10", style(LineCap::Square)), 24.0);
+        // two half discs of radius 1
+        assert_close(area("M0 0 H10", style(LineCap::Round)), 20.0 + PI);
+    }
+
+    #[test]
+    fn miters_longer_than_the_limit_are_beveled() {
+        // the corner is a square of 1 mitered, half of it beveled; the legs overlap by 1
+        let corner = "M0 0 H10 V10";
+        assert_close(area(corner, style(LineCap::Butt)), 40.0);
+
+        let limited = StrokeStyle {
+            miter_limit: 1.4,
+            ..style(LineCap::Butt)
+        };
+        assert_close(area(corner, limited), 39.5);
+
+        let bevel = StrokeStyle {
+            join: LineJoin::Bevel,
+            ..style(LineCap::Butt)
+        };
+        assert_close(area(corner, bevel), 39.5);
+    }
+
+    #[test]
+    fn zero_length_subpaths_are_dots() {
+        assert_close(area("M5 5 L5 5", style(LineCap::Round)), PI);
+        assert_close(area("M5 5 Z", style(LineCap::Square)), 4.0);
+        assert_close(area("M5 5 L5 5", style(LineCap::Butt)), 0.0);
+        // a lone moveto paints nothing
+        assert_close(area("M5 5", style(LineCap::Round)), 0.0);
+    }
+
+    #[test]
+    fn closed_subpaths_are_annuli() {
+        let outline = Path::init("M0 0 H10 V10 H0 Z").stroke_outline(&style(LineCap::Round));
+        let subpaths = outline.subpaths();
+
+        assert_eq!(subpaths.len(), 2);
+        assert_close(subpaths[0].signed_area().abs(), 144.0);
+        assert_close(subpaths[1].signed_area().abs(), 64.0);
+        // the hole runs the other way round
+        assert_close(outline.signed_area(), 80.0);
+    }
+}